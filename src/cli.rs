@@ -0,0 +1,150 @@
+// src/cli.rs
+use std::error::Error;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::lyrics::{self, LyricsCache, TrackQuery};
+use crate::navidrome;
+
+const USAGE: &str = "\
+usage: sonix_lyrics                         start the TUI
+       sonix_lyrics export <song-id> [file]  write synced lyrics as .lrc, .srt or .vtt
+       sonix_lyrics cache list               cached entries, most recently used first
+       sonix_lyrics cache show <key>         one entry and its lyrics
+       sonix_lyrics cache rm <key>...        delete entries (pinned ones too)
+       sonix_lyrics cache purge [--all]      drop expired entries and misses (--all: all unpinned)
+       sonix_lyrics cache stats              entry counts and size on disk";
+
+/// Non-interactive subcommands, run instead of the TUI when arguments are given.
+/// Never starts the setup wizard: with no config yet this fails instead of prompting.
+pub fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let cmd = args.first().map(String::as_str);
+    if !matches!(cmd, Some("export" | "cache")) {
+        return Err(USAGE.into());
+    }
+    let cfg = Config::load_existing().ok_or("no config yet — run sonix_lyrics without arguments to set it up")?;
+    match cmd {
+        Some("export") => export(&cfg, &args[1..]),
+        _ => cache(&LyricsCache::new(&cfg), &args[1..]),
+    }
+}
+
+/// Without a file, writes `Artist - Title.<EXPORT_FORMAT>` into EXPORT_DIR.
+fn export(cfg: &Config, args: &[String]) -> Result<(), Box<dyn Error>> {
+    let Some(id) = args.first() else { return Err(USAGE.into()) };
+    let track = TrackQuery::from(&navidrome::get_song(cfg, id)?);
+
+    let providers = lyrics::providers_from_config(cfg);
+    let ld = lyrics::fetch_lyrics(&providers, &LyricsCache::new(cfg), &track)?;
+
+    let path = match args.get(1) {
+        Some(p) => PathBuf::from(p),
+        None => cfg.export_path().join(lyrics::export_file_name(&track, &cfg.export_format)),
+    };
+    lyrics::export_lyrics(&path, &ld, &track, lyrics::load_offset(&track))?;
+    println!("{}", path.display());
+    Ok(())
+}
+
+fn cache(cache: &LyricsCache, args: &[String]) -> Result<(), Box<dyn Error>> {
+    match (args.first().map(String::as_str), &args[args.len().min(1)..]) {
+        (Some("list"), []) => {
+            for (key, e) in cache.entries() {
+                println!(
+                    "{:<40} {:<12} {:<9} {:>4} {:<6} {} — {}",
+                    key,
+                    e.kind(),
+                    e.provider.as_deref().unwrap_or("-"),
+                    e.age(),
+                    if e.pinned { "pinned" } else { "" },
+                    e.artist,
+                    if e.title.is_empty() { "?" } else { &e.title }
+                );
+            }
+        }
+        (Some("show"), [key]) => {
+            let (e, ld) = cache.entry(key).ok_or_else(|| format!("no cache entry {}", key))?;
+            println!("{} — {}{}", e.artist, e.title, if e.album.is_empty() { String::new() } else { format!(" · {}", e.album) });
+            println!("song id:   {}", if e.id.is_empty() { "-" } else { &e.id });
+            println!("provider:  {} ({})", e.provider.as_deref().unwrap_or("-"), e.source_id.as_deref().unwrap_or("-"));
+            println!("lyrics:    {}{}", e.kind(), if e.pinned { ", pinned" } else { "" });
+            println!("fetched:   {} ({} ago)", e.fetched_at.format("%Y-%m-%d %H:%M"), e.age());
+            let Some(ld) = ld else { return Ok(()) };
+            println!();
+            if ld.synced.is_empty() {
+                ld.lines.iter().for_each(|l| println!("{}", l));
+            } else {
+                for l in &ld.synced {
+                    println!("[{:02}:{:05.2}] {}", l.time_ms / 60_000, (l.time_ms % 60_000) as f64 / 1000.0, l.text);
+                }
+            }
+        }
+        (Some("rm"), keys) if !keys.is_empty() => {
+            for key in keys {
+                if !cache.remove(key) {
+                    eprintln!("no cache entry {}", key);
+                }
+            }
+        }
+        (Some("purge"), rest) if rest.is_empty() || rest == ["--all"] => {
+            println!("removed {} entries", cache.purge(!rest.is_empty()));
+        }
+        (Some("stats"), []) => {
+            let st = cache.stats();
+            println!(
+                "entries:   {} ({} synced, {} plain, {} instrumental, {} not found)",
+                st.entries, st.synced, st.plain, st.instrumental, st.not_found
+            );
+            println!("pinned:    {}", st.pinned);
+            println!("expired:   {}", st.expired);
+            println!("size:      {:.1} KiB", st.bytes as f64 / 1024.0);
+        }
+        _ => return Err(USAGE.into()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lyrics::tests::{temp_cache, track};
+    use crate::lyrics::LyricsData;
+
+    fn args(a: &[&str]) -> Vec<String> {
+        a.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn unknown_commands_print_usage_before_loading_config() {
+        for a in [&[][..], &["bogus"], &["--help"]] {
+            assert_eq!(run(&args(a)).unwrap_err().to_string(), USAGE);
+        }
+    }
+
+    #[test]
+    fn cache_subcommands() {
+        let (cache, _dir) = temp_cache("cli", 0);
+        let pinned = LyricsData { lines: vec!["la".into()], pinned: true, ..Default::default() };
+        cache.store(&track("keep"), &pinned);
+        cache.store(&track("drop"), &LyricsData { lines: vec!["la".into()], ..Default::default() });
+        cache.store_not_found(&track("missing"));
+
+        for bad in [&["rm"][..], &["purge", "--everything"], &["show"], &["list", "extra"], &["nope"]] {
+            assert!(cache_cmd(&cache, bad).is_err(), "{:?}", bad);
+        }
+        assert!(cache_cmd(&cache, &["show", "no-such-key"]).is_err());
+
+        cache_cmd(&cache, &["purge"]).unwrap();
+        assert_eq!(cache.entries().len(), 2);
+        cache_cmd(&cache, &["purge", "--all"]).unwrap();
+        let keys: Vec<String> = cache.entries().into_iter().map(|(k, _)| k).collect();
+        assert_eq!(keys.len(), 1);
+        cache_cmd(&cache, &["show", &keys[0]]).unwrap();
+        cache_cmd(&cache, &["rm", &keys[0]]).unwrap();
+        assert!(cache.entries().is_empty());
+    }
+
+    fn cache_cmd(cache: &LyricsCache, a: &[&str]) -> Result<(), Box<dyn Error>> {
+        super::cache(cache, &args(a))
+    }
+}
@@ -14,9 +14,79 @@ pub struct Config {
 
     pub refresh_interval: u64,
     pub karaoke_enabled: bool,
+    /// Translation / romanization rows under the lyrics, when the lyrics have them.
+    pub show_translations: bool,
+    /// Add a romanized row for kana, hangul and Cyrillic lyrics.
+    pub romanize: bool,
+    pub follow_mode: bool,
+
+    /// Lyrics providers in the order they are tried, e.g. `navidrome,lrclib`.
+    pub lyrics_providers: Vec<String>,
+    pub lrclib_url: String,
+    /// Directory of hand-made lyrics files (`.lrc`, `.srt`, `.vtt`, `.ttml`, `.txt`)
+    /// for the `local` provider.
+    pub local_lyrics_dir: String,
+    /// Optional path under `local_lyrics_dir`, e.g. `{artist}/{album}/{title}`.
+    pub local_lyrics_template: String,
+
+    /// Where the export key writes subtitle files.
+    pub export_dir: String,
+    /// `srt`, `vtt` or `lrc`.
+    pub export_format: String,
+
+    /// Days before cached lyrics are refetched; 0 keeps them forever.
+    pub cache_ttl_days: u64,
+    /// Hours before a track nobody had lyrics for is looked up again.
+    pub cache_negative_ttl_hours: u64,
+    /// Least recently used entries beyond this are dropped; 0 means no limit.
+    pub cache_max_entries: usize,
+
+    /// Upcoming queue entries whose lyrics and cover art are fetched ahead of time.
+    pub prefetch_tracks: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            navidrome_url: String::new(),
+            navidrome_user: String::new(),
+            navidrome_token: String::new(),
+            navidrome_salt: String::new(),
+
+            refresh_interval: 2,
+            karaoke_enabled: true,
+            show_translations: true,
+            romanize: false,
+            follow_mode: false,
+
+            lyrics_providers: vec!["local".into(), "navidrome".into(), "lrclib".into()],
+            lrclib_url: "https://lrclib.net".into(),
+            local_lyrics_dir: "~/.local/share/sonix_lyrics/lyrics".into(),
+            local_lyrics_template: String::new(),
+
+            export_dir: "~/.local/share/sonix_lyrics/exports".into(),
+            export_format: "srt".into(),
+
+            cache_ttl_days: 30,
+            cache_negative_ttl_hours: 24,
+            cache_max_entries: 5000,
+
+            prefetch_tracks: 2,
+        }
+    }
 }
 
 impl Config {
+    /// `local_lyrics_dir` with a leading `~` expanded.
+    pub fn local_lyrics_path(&self) -> PathBuf {
+        expand_home(&self.local_lyrics_dir)
+    }
+
+    /// `export_dir` with a leading `~` expanded.
+    pub fn export_path(&self) -> PathBuf {
+        expand_home(&self.export_dir)
+    }
+
     fn config_path() -> PathBuf {
         let home = std::env::var("HOME").expect("HOME not set");
         PathBuf::from(format!("{}/.config/sonix_lyrics/config.conf", home))
@@ -36,9 +106,18 @@ impl Config {
         Self::load()
     }
 
+    /// The saved config without falling back to the wizard, for non-interactive runs.
+    pub fn load_existing() -> Option<Self> {
+        Self::config_path().exists().then(Self::load)
+    }
+
     pub fn load() -> Self {
         let contents = fs::read_to_string(Self::config_path()).expect("Failed to read config.conf");
+        Self::parse(&contents)
+    }
 
+    /// `KEY = value` lines; `#` comments and blank lines are skipped.
+    fn parse(contents: &str) -> Self {
         let mut map = HashMap::new();
 
         for line in contents.lines() {
@@ -67,6 +146,76 @@ impl Config {
                 .get("KARAOKE_ENABLED")
                 .map(|v| v.eq_ignore_ascii_case("true"))
                 .unwrap_or(true),
+
+            show_translations: map
+                .get("SHOW_TRANSLATIONS")
+                .map(|v| v.eq_ignore_ascii_case("true"))
+                .unwrap_or(true),
+
+            romanize: map
+                .get("ROMANIZE")
+                .map(|v| v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false),
+
+            follow_mode: map
+                .get("FOLLOW_MODE")
+                .map(|v| v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false),
+
+            lyrics_providers: map
+                .get("LYRICS_PROVIDERS")
+                .map(|v| {
+                    v.split(',')
+                        .map(|p| p.trim().to_lowercase())
+                        .filter(|p| !p.is_empty())
+                        .collect()
+                })
+                .unwrap_or_else(|| Config::default().lyrics_providers),
+
+            lrclib_url: map
+                .get("LRCLIB_URL")
+                .map(|v| v.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| Config::default().lrclib_url),
+
+            local_lyrics_dir: map
+                .get("LOCAL_LYRICS_DIR")
+                .cloned()
+                .unwrap_or_else(|| Config::default().local_lyrics_dir),
+
+            local_lyrics_template: map
+                .get("LOCAL_LYRICS_TEMPLATE")
+                .cloned()
+                .unwrap_or_default(),
+
+            export_dir: map
+                .get("EXPORT_DIR")
+                .cloned()
+                .unwrap_or_else(|| Config::default().export_dir),
+
+            export_format: map
+                .get("EXPORT_FORMAT")
+                .map(|v| v.to_lowercase())
+                .unwrap_or_else(|| Config::default().export_format),
+
+            cache_ttl_days: map
+                .get("CACHE_TTL_DAYS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Config::default().cache_ttl_days),
+
+            cache_negative_ttl_hours: map
+                .get("CACHE_NEGATIVE_TTL_HOURS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Config::default().cache_negative_ttl_hours),
+
+            cache_max_entries: map
+                .get("CACHE_MAX_ENTRIES")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Config::default().cache_max_entries),
+
+            prefetch_tracks: map
+                .get("PREFETCH_TRACKS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Config::default().prefetch_tracks),
         }
     }
 
@@ -85,6 +234,23 @@ NAVIDROME_SALT = {}
 
 REFRESH_INTERVAL = {}
 KARAOKE_ENABLED = {}
+SHOW_TRANSLATIONS = {}
+ROMANIZE = {}
+FOLLOW_MODE = {}
+
+LYRICS_PROVIDERS = {}
+LRCLIB_URL = {}
+LOCAL_LYRICS_DIR = {}
+LOCAL_LYRICS_TEMPLATE = {}
+
+EXPORT_DIR = {}
+EXPORT_FORMAT = {}
+
+CACHE_TTL_DAYS = {}
+CACHE_NEGATIVE_TTL_HOURS = {}
+CACHE_MAX_ENTRIES = {}
+
+PREFETCH_TRACKS = {}
 "#,
             self.navidrome_url,
             self.navidrome_user,
@@ -92,8 +258,44 @@ KARAOKE_ENABLED = {}
             self.navidrome_salt,
             self.refresh_interval,
             self.karaoke_enabled,
+            self.show_translations,
+            self.romanize,
+            self.follow_mode,
+            self.lyrics_providers.join(","),
+            self.lrclib_url,
+            self.local_lyrics_dir,
+            self.local_lyrics_template,
+            self.export_dir,
+            self.export_format,
+            self.cache_ttl_days,
+            self.cache_negative_ttl_hours,
+            self.cache_max_entries,
+            self.prefetch_tracks,
         );
 
         fs::write(path, data).expect("Failed to write config file");
     }
 }
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REQUIRED: &str = "NAVIDROME_URL = http://nd\nNAVIDROME_USER = u\nNAVIDROME_TOKEN = t\nNAVIDROME_SALT = s\n";
+
+    #[test]
+    fn lyrics_providers_keep_their_order() {
+        let cfg = Config::parse(&format!("{}# tried in this order\nLYRICS_PROVIDERS = \" LRCLIB, bogus ,local,, \"\n", REQUIRED));
+        assert_eq!(cfg.lyrics_providers, vec!["lrclib", "bogus", "local"]);
+
+        let cfg = Config::parse(REQUIRED);
+        assert_eq!(cfg.lyrics_providers, Config::default().lyrics_providers);
+    }
+}
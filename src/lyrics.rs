@@ -1,8 +1,17 @@
 use reqwest::blocking::{Client, ClientBuilder};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock};
 use std::time::Duration;
 use thiserror::Error;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::config::Config;
+use crate::navidrome::{self, NavidromeError, PlaylistTrack, StructuredLyrics};
+use crate::romanize::romanize;
 
 #[derive(Debug, Error)]
 pub enum LyricsError {
@@ -12,16 +21,44 @@ pub enum LyricsError {
     #[error("JSON: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("Navidrome: {0}")]
+    Navidrome(#[from] NavidromeError),
+
     #[error("Not found")]
     NotFound,
+
+    #[error("I/O: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Lyrics are not synced")]
+    NotSynced,
+
+    #[error("Unsupported export format \"{0}\" (use lrc, srt or vtt)")]
+    ExportFormat(String),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KaraokeWord {
     pub time_ms: u32,
+    /// When the word is fully sung: the next word, a trailing tag, or the next line.
+    #[serde(default)]
+    pub end_ms: u32,
+    /// Includes the whitespace after it, so the words of a line join back into its text.
     pub word: String,
 }
 
+impl KaraokeWord {
+    fn shift(&mut self, offset_ms: i64) {
+        self.time_ms = shift_ms(self.time_ms, offset_ms);
+        if self.end_ms != 0 {
+            self.end_ms = shift_ms(self.end_ms, offset_ms);
+        }
+    }
+}
+
+/// How long the last word of the last line lasts when nothing says otherwise.
+const LAST_WORD_MS: u32 = 1000;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncedLine {
     pub time_ms: u32,
@@ -29,20 +66,132 @@ pub struct SyncedLine {
     pub words: Vec<KaraokeWord>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LyricsData {
     pub lines: Vec<String>,
     pub synced: Vec<SyncedLine>,
+    #[serde(default)]
+    pub source: Option<Provenance>,
+    #[serde(default)]
+    pub meta: LrcMetadata,
+    /// Chosen by hand; automatic lookups must not replace it.
+    #[serde(default)]
+    pub pinned: bool,
+    /// The track has no vocals; `lines` and `synced` are empty on purpose.
+    #[serde(default)]
+    pub instrumental: bool,
+    /// Translations / romanizations shown under the synced lines.
+    #[serde(default)]
+    pub parallel: Vec<ParallelLines>,
+}
+
+/// A second row for every synced line, e.g. a translation or a romanization.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParallelLines {
+    /// `translation`, or a language / label taken from the source.
+    pub label: String,
+    /// One per `LyricsData::synced` line; empty where there is nothing to show.
+    pub lines: Vec<String>,
+}
+
+/// LRC ID tags. `offset_ms` has already been applied to every line and word.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LrcMetadata {
+    pub artist: Option<String>,
+    pub title: Option<String>,
+    pub album: Option<String>,
+    pub length_ms: Option<u32>,
+    pub offset_ms: i64,
+}
+
+/// Where a set of lyrics came from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    pub provider: String,
+    /// Provider-specific id of the matched record, if it has one.
+    pub source_id: Option<String>,
+}
+
+/// The track lyrics are being looked up for.
+#[derive(Debug, Clone, Default)]
+pub struct TrackQuery {
+    /// Navidrome song id
+    pub id: String,
+    pub artist: String,
+    pub title: String,
+    pub album: String,
+    /// Seconds, 0 when unknown.
+    pub duration: u32,
+    /// Tagged instrumental on the server (genre or mood).
+    pub instrumental: bool,
+}
+
+impl From<&PlaylistTrack> for TrackQuery {
+    fn from(pt: &PlaylistTrack) -> Self {
+        TrackQuery {
+            id: pt.id.clone(),
+            artist: pt.artist.clone(),
+            title: pt.title.clone(),
+            album: pt.album.clone(),
+            duration: pt.duration,
+            instrumental: pt.instrumental,
+        }
+    }
+}
+
+/// One search hit from a provider, with its lyrics already attached.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub artist: String,
+    pub title: String,
+    pub album: String,
+    pub duration: u32,
+    pub instrumental: bool,
+    pub lyrics: LyricsData,
+}
+
+pub trait LyricsProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Free-text search. Providers that can only look up exact tracks return nothing.
+    fn search(&self, _query: &str) -> Result<Vec<Candidate>, LyricsError> {
+        Ok(vec![])
+    }
+
+    /// Best lyrics for a track, or `NotFound`.
+    fn fetch(&self, track: &TrackQuery) -> Result<LyricsData, LyricsError>;
+
+    /// Lower-confidence lookup, tried only after every provider's `fetch` has missed.
+    fn fallback(&self, _track: &TrackQuery) -> Result<LyricsData, LyricsError> {
+        Err(LyricsError::NotFound)
+    }
+
+    /// Cheap, always-current sources are consulted before the cache and never cached.
+    fn is_local(&self) -> bool {
+        false
+    }
 }
 
 #[derive(Debug, Deserialize)]
 struct LrcLibResult {
+    #[serde(default)]
+    id: u64,
+
     #[serde(rename = "trackName")]
     track: String,
 
     #[serde(rename = "artistName")]
     artist: String,
 
+    #[serde(rename = "albumName", default)]
+    album: Option<String>,
+
+    #[serde(default)]
+    duration: Option<f32>,
+
+    #[serde(default)]
+    instrumental: bool,
+
     #[serde(rename = "plainLyrics")]
     plain: Option<String>,
 
@@ -59,107 +208,455 @@ fn http() -> Client {
         .unwrap()
 }
 
+/// `mm:ss`, `mm:ss.xx`, `mm:ss.xxx`, `mm:ss:xx` and `hh:mm:ss.xx` to milliseconds.
+/// Three fields without a `.` are read as `mm:ss:xx` (centiseconds), the common LRC variant.
 fn parse_ts(ts: &str) -> Option<u32> {
-    let mut parts = ts.split(':');
-    let m: u32 = parts.next()?.parse().ok()?;
-    let s: f32 = parts.next()?.parse().ok()?;
+    let parts: Vec<&str> = ts.trim().split(':').collect();
+    let (h, m, sec) = match parts.as_slice() {
+        [m, s] => (0, digits(m)?, *s),
+        [h, m, s] if s.contains('.') => (digits(h)?, digits(m)?, *s),
+        [m, s, cs] => {
+            let s = digits(s)?;
+            if s >= 60 {
+                return None;
+            }
+            return Some(digits(m)? * 60_000 + s * 1000 + fraction_ms(cs)?);
+        }
+        _ => return None,
+    };
 
-    Some(m * 60_000 + (s * 1000.0).round() as u32)
+    let (s, frac) = match sec.split_once('.') {
+        Some((s, f)) => (digits(s)?, fraction_ms(f)?),
+        None => (digits(sec)?, 0),
+    };
+    if s >= 60 || (h > 0 && m >= 60) {
+        return None;
+    }
+    Some(h * 3_600_000 + m * 60_000 + s * 1000 + frac)
 }
 
-fn parse_karaoke_words(text: &str) -> Vec<KaraokeWord> {
-    let mut out = Vec::new();
-    let chars = text.as_bytes();
-    let mut i = 0;
+fn digits(s: &str) -> Option<u32> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
 
-    while i < chars.len() {
-        if chars[i] == b'<' {
-            let mut j = i + 1;
-            while j < chars.len() && chars[j] != b'>' {
-                j += 1;
-            }
-            if j >= chars.len() {
-                break;
-            }
+/// Fractional seconds to milliseconds: "5" → 500, "05" → 50, "005" → 5.
+fn fraction_ms(f: &str) -> Option<u32> {
+    if f.is_empty() || f.len() > 3 {
+        return None;
+    }
+    Some(digits(f)? * 10u32.pow(3 - f.len() as u32))
+}
 
-            let ts = &text[i + 1..j];
-            let time = match parse_ts(ts) {
-                Some(t) => t,
-                None => {
-                    i = j + 1;
-                    continue;
-                }
-            };
+/// Apply a lyrics offset in milliseconds (positive = show sooner).
+fn shift_ms(ms: u32, offset_ms: i64) -> u32 {
+    (ms as i64 - offset_ms).max(0) as u32
+}
+
+/// Enhanced LRC (A2): `<mm:ss.xx>word <mm:ss.xx>word <mm:ss.xx>`.
+/// A tag with no word after it only ends the previous word. Untimed text before the
+/// first timestamp starts at `line_ms`. With `markup`, other tags (`<i>`, WebVTT
+/// `<v Name>`) are dropped; without, they are just text. The last word's end is
+/// filled in by `fill_word_ends`.
+fn parse_karaoke_words(text: &str, line_ms: u32, markup: bool) -> Vec<KaraokeWord> {
+    let (lead, tags) = split_tags(text, markup);
+    if !tags.iter().any(|(tag, _)| parse_ts(tag).is_some()) {
+        return vec![];
+    }
+
+    let mut out: Vec<KaraokeWord> = Vec::new();
+    if !lead.trim().is_empty() {
+        out.push(KaraokeWord { time_ms: line_ms, end_ms: 0, word: lead.to_string() });
+    }
+
+    for (tag, seg) in tags {
+        let Some(time) = parse_ts(tag) else {
+            // not a timestamp: keep the text with whatever word it follows
+            match out.last_mut() {
+                Some(prev) => prev.word.push_str(seg),
+                None if !seg.is_empty() => out.push(KaraokeWord { time_ms: line_ms, end_ms: 0, word: seg.to_string() }),
+                None => {}
+            }
+            continue;
+        };
 
-            let mut k = j + 1;
-            while k < chars.len() && chars[k] != b'<' {
-                k += 1;
+        if let Some(prev) = out.last_mut() {
+            if prev.end_ms == 0 {
+                prev.end_ms = time;
             }
+        }
 
-            let w = text[j + 1..k].trim();
-            if !w.is_empty() {
-                out.push(KaraokeWord {
-                    time_ms: time,
-                    word: w.to_string(),
-                });
+        if seg.trim().is_empty() {
+            if let Some(prev) = out.last_mut() {
+                prev.word.push_str(seg);
             }
-            i = k;
         } else {
-            i += 1;
+            out.push(KaraokeWord { time_ms: time, end_ms: 0, word: seg.to_string() });
         }
     }
     out
 }
 
-fn strip_karaoke_tags(text: &str) -> String {
-    let mut out = String::new();
-    let bytes = text.as_bytes();
-    let mut i = 0;
-    while i < bytes.len() {
-        if bytes[i] == b'<' {
-            while i < bytes.len() && bytes[i] != b'>' {
-                i += 1;
+/// Split a line at its `<…>` tags: the text before the first tag, then each tag's
+/// contents paired with the text up to the next tag. Without `markup` only timestamp
+/// tags count, so lyrics like "<3 you >" stay whole. A `<` that is never closed is
+/// left in the text. Slices are taken at char boundaries only.
+fn split_tags(text: &str, markup: bool) -> (&str, Vec<(&str, &str)>) {
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    let mut open = None;
+    for (i, c) in text.char_indices() {
+        match c {
+            // a stray `<` in lyrics text may come right before a real tag
+            '<' if open.is_none() || !markup => open = Some(i),
+            '>' => {
+                if let Some(o) = open.take() {
+                    if markup || parse_ts(&text[o + 1..i]).is_some() {
+                        spans.push((o, i));
+                    }
+                }
             }
-            i += 1;
-        } else {
-            out.push(bytes[i] as char);
-            i += 1;
+            _ => {}
         }
     }
+
+    let Some(&(first, _)) = spans.first() else { return (text, vec![]) };
+    let tags = spans.iter().enumerate().map(|(k, &(o, c))| {
+        let seg_end = spans.get(k + 1).map(|&(next, _)| next).unwrap_or(text.len());
+        (&text[o + 1..c], &text[c + 1..seg_end])
+    }).collect();
+    (&text[..first], tags)
+}
+
+/// End each line's last word at the next line (or `[length:]` for the last line).
+/// `lines` must be sorted.
+fn fill_word_ends(lines: &mut [SyncedLine], length_ms: Option<u32>) {
+    for i in 0..lines.len() {
+        // a translation sharing the timestamp doesn't end the line
+        let start = lines[i].time_ms;
+        let next = lines[i + 1..].iter().map(|l| l.time_ms).find(|&t| t > start).or(length_ms);
+        for w in lines[i].words.iter_mut().filter(|w| w.end_ms <= w.time_ms) {
+            w.end_ms = next.filter(|&n| n > w.time_ms).unwrap_or(w.time_ms + LAST_WORD_MS);
+        }
+    }
+}
+
+/// The text of a line without its word timestamps (and, with `markup`, other tags).
+fn strip_karaoke_tags(text: &str, markup: bool) -> String {
+    let (lead, tags) = split_tags(text, markup);
+    let mut out = lead.to_string();
+    for (_, seg) in tags {
+        out.push_str(seg);
+    }
     out
 }
 
-fn parse_lrc(text: &str) -> Vec<SyncedLine> {
+fn parse_lrc(text: &str) -> (Vec<SyncedLine>, LrcMetadata) {
     let mut out = Vec::new();
+    let mut meta = LrcMetadata::default();
 
-    for l in text.lines() {
-        if !l.starts_with('[') {
+    // files saved by Windows editors often start with a byte-order mark
+    for l in text.trim_start_matches('\u{feff}').lines() {
+        let mut rest = l.trim();
+        if !rest.starts_with('[') {
             continue;
         }
-        let end = match l.find(']') {
-            Some(i) => i,
-            None => continue,
-        };
-        let ts = &l[1..end];
-        let body = l[end + 1..].trim();
 
-        let t = match parse_ts(ts) {
-            Some(v) => v,
-            None => continue,
-        };
+        // `[00:12.00][01:45.30]Chorus` repeats one line at several times
+        let mut times = Vec::new();
+        while let Some(inner) = rest.strip_prefix('[') {
+            let Some(end) = inner.find(']') else { break };
+            match parse_ts(&inner[..end]) {
+                Some(t) => times.push(t),
+                None if times.is_empty() => parse_id_tag(&inner[..end], &mut meta),
+                None => break,
+            }
+            rest = &inner[end + 1..];
+        }
+        let Some(&first) = times.first() else { continue };
 
-        out.push(SyncedLine {
-            time_ms: t,
-            text: strip_karaoke_tags(body),
-            words: parse_karaoke_words(body),
-        });
+        let body = rest.trim();
+        let text = strip_karaoke_tags(body, false);
+        let words = parse_karaoke_words(body, first, false);
+
+        for t in times {
+            // word tags are absolute; move them along with each repeat
+            let mut words = words.clone();
+            for w in &mut words {
+                w.shift(first as i64 - t as i64);
+            }
+            out.push(SyncedLine { time_ms: t, text: text.clone(), words });
+        }
+    }
+
+    if meta.offset_ms != 0 {
+        for line in &mut out {
+            line.time_ms = shift_ms(line.time_ms, meta.offset_ms);
+            for w in &mut line.words {
+                w.shift(meta.offset_ms);
+            }
+        }
     }
 
     out.sort_by_key(|l| l.time_ms);
+    fill_word_ends(&mut out, meta.length_ms.map(|l| shift_ms(l, meta.offset_ms)));
+    (out, meta)
+}
+
+/// `[ar:…]`, `[ti:…]`, `[al:…]`, `[length:…]`, `[offset:…]`. Other tags are ignored.
+fn parse_id_tag(tag: &str, meta: &mut LrcMetadata) {
+    let Some((key, value)) = tag.split_once(':') else { return };
+    let value = value.trim();
+    if value.is_empty() {
+        return;
+    }
+    match key.trim().to_ascii_lowercase().as_str() {
+        "ar" => meta.artist = Some(value.to_string()),
+        "ti" => meta.title = Some(value.to_string()),
+        "al" => meta.album = Some(value.to_string()),
+        "length" => meta.length_ms = parse_ts(value),
+        "offset" => meta.offset_ms = value.parse().unwrap_or(0),
+        _ => {}
+    }
+}
+
+/// Why the LRC's own tags say these lyrics belong to a different track, if they do.
+fn metadata_mismatch(meta: &LrcMetadata, track: &TrackQuery) -> Option<String> {
+    if let (Some(len), true) = (meta.length_ms, track.duration > 0) {
+        let delta = (len as i64 / 1000 - track.duration as i64).abs();
+        if delta > 15 {
+            return Some(format!("[length:] is {}s off", delta));
+        }
+    }
+    if let Some(ref ti) = meta.title {
+        if !loosely_equal(ti, &track.title) {
+            return Some(format!("[ti:{}] vs \"{}\"", ti, track.title));
+        }
+    }
+    if let Some(ref ar) = meta.artist {
+        if !loosely_equal(ar, &track.artist) {
+            return Some(format!("[ar:{}] vs \"{}\"", ar, track.artist));
+        }
+    }
+    None
+}
+
+fn loosely_equal(a: &str, b: &str) -> bool {
+    let (a, b) = (normalize(a), normalize(b));
+    a.contains(&b) || b.contains(&a) || similarity(&a, &b) >= 0.6
+}
+
+// ---- SRT / WebVTT / TTML ----
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LyricsFormat {
+    Lrc,
+    Srt,
+    Vtt,
+    Ttml,
+}
+
+impl LyricsFormat {
+    fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_ascii_lowercase().as_str() {
+            "lrc" => Some(Self::Lrc),
+            "srt" => Some(Self::Srt),
+            "vtt" => Some(Self::Vtt),
+            "ttml" => Some(Self::Ttml),
+            _ => None,
+        }
+    }
+
+    /// Guess from the text itself, for providers that don't say what they serve.
+    fn sniff(text: &str) -> Self {
+        let head = text.trim_start_matches('\u{feff}').trim_start();
+        if head.starts_with("WEBVTT") {
+            Self::Vtt
+        } else if head.starts_with('<') && head.contains("<tt") {
+            Self::Ttml
+        } else if head.lines().any(|l| l.contains("-->")) {
+            Self::Srt
+        } else {
+            Self::Lrc
+        }
+    }
+}
+
+fn parse_lyrics(text: &str, format: LyricsFormat) -> (Vec<SyncedLine>, LrcMetadata) {
+    let mut lines = match format {
+        LyricsFormat::Lrc => return parse_lrc(text),
+        LyricsFormat::Srt | LyricsFormat::Vtt => parse_cues(text),
+        LyricsFormat::Ttml => parse_ttml(text),
+    };
+    lines.sort_by_key(|l| l.time_ms);
+    fill_word_ends(&mut lines, None);
+    (lines, LrcMetadata::default())
+}
+
+/// A timed line with an explicit end. The last word ends with the cue, and a blank
+/// line is added at the end so the lyric clears during gaps between cues.
+fn push_cue(out: &mut Vec<SyncedLine>, start: u32, end: Option<u32>, text: String, mut words: Vec<KaraokeWord>) {
+    if let (Some(end), Some(last)) = (end, words.last_mut()) {
+        if last.end_ms == 0 && end > last.time_ms {
+            last.end_ms = end;
+        }
+    }
+    out.push(SyncedLine { time_ms: start, text, words });
+    if let Some(end) = end.filter(|&e| e > start) {
+        out.push(SyncedLine { time_ms: end, text: String::new(), words: vec![] });
+    }
+}
+
+/// Drop the blank line `push_cue` added when the next cue starts right away (or earlier).
+fn merge_cue_gaps(lines: &mut Vec<SyncedLine>) {
+    lines.sort_by_key(|l| l.time_ms);
+    let mut i = 0;
+    while i + 1 < lines.len() {
+        if lines[i].text.is_empty() && lines[i].words.is_empty() && lines[i + 1].time_ms <= lines[i].time_ms {
+            lines.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// SRT and WebVTT cues. Multi-line cues are joined with a space; WebVTT inline
+/// `<hh:mm:ss.mmm>` timestamps become karaoke words.
+fn parse_cues(text: &str) -> Vec<SyncedLine> {
+    let mut out = Vec::new();
+    let text = text.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+
+    for block in text.split("\n\n") {
+        let mut rows = block.lines().map(str::trim).filter(|l| !l.is_empty());
+        // optional cue number / identifier before the timing row
+        let Some(timing) = rows.by_ref().find(|l| l.contains("-->")) else { continue };
+        let Some((from, to)) = timing.split_once("-->") else { continue };
+        let start = cue_time(from);
+        // WebVTT cue settings follow the end time
+        let end = cue_time(to.split_whitespace().next().unwrap_or_default());
+        let Some(start) = start else { continue };
+
+        let body = rows.map(strip_srt_overrides).collect::<Vec<_>>().join(" ");
+        let words = parse_karaoke_words(&body, start, true);
+        push_cue(&mut out, start, end, strip_karaoke_tags(&body, true), words);
+    }
+    merge_cue_gaps(&mut out);
+    out
+}
+
+/// `00:01:02,500` (SRT) or `01:02.500` / `00:01:02.500` (WebVTT).
+fn cue_time(s: &str) -> Option<u32> {
+    parse_ts(&s.trim().replace(',', "."))
+}
+
+/// SSA-style `{\an8}` positioning that some SRT files carry.
+fn strip_srt_overrides(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut depth = 0;
+    for c in line.chars() {
+        match c {
+            '{' if line.contains("{\\") => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            _ if depth == 0 => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// TTML as served by Apple Music and others: one `<p begin= end=>` per line, with
+/// optional word timing in `<span begin= end=>`. Background vocals and
+/// translation/romanization spans are skipped.
+fn parse_ttml(text: &str) -> Vec<SyncedLine> {
+    let doc = match roxmltree::Document::parse(text.trim_start_matches('\u{feff}')) {
+        Ok(doc) => doc,
+        Err(e) => {
+            log::warn!("Invalid TTML: {}", e);
+            return vec![];
+        }
+    };
+
+    let mut out = Vec::new();
+    for p in doc.descendants().filter(|n| n.has_tag_name("p")) {
+        let mut line = String::new();
+        let mut lead = String::new(); // untimed text before the first timed span
+        let mut words: Vec<KaraokeWord> = Vec::new();
+        for child in p.children() {
+            let text: String = if child.is_text() {
+                child.text().unwrap_or_default().to_string()
+            } else if child.has_tag_name("br") {
+                " ".into()
+            } else if child.has_tag_name("span") && ttml_role(child).is_none() {
+                child.descendants().filter(|n| n.is_text()).filter_map(|n| n.text()).collect()
+            } else {
+                continue;
+            };
+            line.push_str(&text);
+
+            match (child.is_element().then(|| ttml_attr(child, "begin")).flatten(), words.last_mut()) {
+                (Some(begin), _) => words.push(KaraokeWord {
+                    time_ms: begin,
+                    end_ms: ttml_attr(child, "end").unwrap_or(0),
+                    word: text,
+                }),
+                (None, Some(prev)) => prev.word.push_str(&text),
+                (None, None) => lead.push_str(&text),
+            }
+        }
+
+        let Some(start) = ttml_attr(p, "begin").or(words.first().map(|w| w.time_ms)) else { continue };
+        if !words.is_empty() && !lead.trim().is_empty() {
+            let end_ms = words[0].time_ms;
+            words.insert(0, KaraokeWord { time_ms: start, end_ms, word: lead });
+        }
+        push_cue(&mut out, start, ttml_attr(p, "end"), line, words);
+    }
+    merge_cue_gaps(&mut out);
     out
 }
 
+fn ttml_role<'a>(node: roxmltree::Node<'a, '_>) -> Option<&'a str> {
+    node.attributes().find(|a| a.name() == "role").map(|a| a.value())
+}
+
+fn ttml_attr(node: roxmltree::Node, name: &str) -> Option<u32> {
+    ttml_time(node.attribute(name)?)
+}
+
+/// TTML clock values: `12.345s`, `850ms`, `1:02.5`, `00:01:02.500` or bare seconds.
+fn ttml_time(s: &str) -> Option<u32> {
+    let s = s.trim();
+    if let Some(ms) = s.strip_suffix("ms") {
+        return ms.parse::<f64>().ok().map(|v| v.round() as u32);
+    }
+    let secs = |v: &str| v.parse::<f64>().ok().filter(|v| *v >= 0.0);
+    let total = match s.rsplit_once(':') {
+        Some((hm, sec)) => {
+            let (h, m) = match hm.split_once(':') {
+                Some((h, m)) => (digits(h)?, digits(m)?),
+                None => (0, digits(hm)?),
+            };
+            (h * 3600 + m * 60) as f64 + secs(sec)?
+        }
+        None => secs(s.strip_suffix('s').unwrap_or(s))?,
+    };
+    Some((total * 1000.0).round() as u32)
+}
+
 // ---- lyrics cache ----
+// One JSON file per track, plus `index.json` recording where each entry came from
+// and when it was fetched and last used, for expiry and eviction. Entries are keyed
+// by Navidrome song id plus a hash of the tags; the index doubles as an
+// artist/title/duration lookup.
+
+/// Bump when the on-disk layout changes. Unversioned files are version 1, keyed by
+/// `sanitize(artist)_sanitize(title)`.
+const CACHE_VERSION: u32 = 2;
+
+fn cache_dir() -> PathBuf {
+    PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(".cache/sonix_lyrics")
+}
 
 fn sanitize(s: &str) -> String {
     s.chars()
@@ -168,74 +665,2245 @@ fn sanitize(s: &str) -> String {
         .to_lowercase()
 }
 
-fn cache_path(artist: &str, title: &str) -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_default();
-    let key = format!("{}_{}", sanitize(artist), sanitize(title));
-    PathBuf::from(format!("{}/.cache/sonix_lyrics/{}.json", home, key))
+/// `id-<song id>-<hash of artist and title>`, so a retagged file doesn't keep the old
+/// song's lyrics; for tracks without an id a hash of artist, title and duration.
+fn cache_key(track: &TrackQuery) -> String {
+    if !track.id.is_empty() {
+        let tags = format!("{:x}", md5::compute(format!("{}\u{1f}{}", track.artist, track.title)));
+        let id = if track.id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            track.id.clone()
+        } else {
+            format!("{:x}", md5::compute(&track.id))
+        };
+        format!("id-{}-{}", id, &tags[..8])
+    } else {
+        let meta = format!("{}\u{1f}{}\u{1f}{}", track.artist, track.title, track.duration);
+        format!("meta-{:x}", md5::compute(meta))
+    }
+}
+
+/// The version 1 key, which folded "AC/DC" and "AC DC" together.
+fn legacy_key(artist: &str, title: &str) -> String {
+    format!("{}_{}", sanitize(artist), sanitize(title))
 }
 
-fn load_cache(artist: &str, title: &str) -> Option<LyricsData> {
-    let data = std::fs::read_to_string(cache_path(artist, title)).ok()?;
-    serde_json::from_str(&data).ok()
+/// What each cache file holds: the lyrics plus the layout version.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheFile {
+    #[serde(default = "legacy_version")]
+    version: u32,
+    #[serde(flatten)]
+    lyrics: LyricsData,
 }
 
-fn save_cache(artist: &str, title: &str, ld: &LyricsData) {
-    let path = cache_path(artist, title);
-    if let Some(dir) = path.parent() {
-        let _ = std::fs::create_dir_all(dir);
+fn legacy_version() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// Navidrome song id, empty when the track had none.
+    #[serde(default)]
+    pub id: String,
+    pub artist: String,
+    pub title: String,
+    #[serde(default)]
+    pub album: String,
+    /// Seconds, 0 when unknown.
+    #[serde(default)]
+    pub duration: u32,
+    pub provider: Option<String>,
+    pub source_id: Option<String>,
+    pub fetched_at: DateTime<Utc>,
+    pub last_used: DateTime<Utc>,
+    #[serde(default)]
+    pub pinned: bool,
+    /// No provider had lyrics. Only the miss is stored, with a shorter TTL.
+    #[serde(default)]
+    pub not_found: bool,
+    #[serde(default)]
+    pub synced: bool,
+    #[serde(default)]
+    pub instrumental: bool,
+}
+
+impl CacheEntry {
+    /// The track to look up again when refetching.
+    pub fn track(&self) -> TrackQuery {
+        TrackQuery {
+            id: self.id.clone(),
+            artist: self.artist.clone(),
+            title: self.title.clone(),
+            album: self.album.clone(),
+            duration: self.duration,
+            ..Default::default()
+        }
+    }
+
+    /// Files recovered without an index entry only know a title if they had `[ti:]`.
+    pub fn refetchable(&self) -> bool {
+        !self.title.is_empty()
+    }
+
+    /// `synced`, `plain`, `instrumental` or `not found`.
+    pub fn kind(&self) -> &'static str {
+        if self.not_found {
+            "not found"
+        } else if self.instrumental {
+            "instrumental"
+        } else if self.synced {
+            "synced"
+        } else {
+            "plain"
+        }
     }
-    if let Ok(json) = serde_json::to_string(ld) {
-        let _ = std::fs::write(path, json);
+
+    /// Time since fetched, e.g. `3d`, `5h`, `12m`.
+    pub fn age(&self) -> String {
+        let d = Utc::now() - self.fetched_at;
+        if d.num_days() > 0 {
+            format!("{}d", d.num_days())
+        } else if d.num_hours() > 0 {
+            format!("{}h", d.num_hours())
+        } else {
+            format!("{}m", d.num_minutes().max(0))
+        }
     }
 }
 
-// ---- lrclib search ----
+#[derive(Debug, Default)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub synced: usize,
+    pub plain: usize,
+    pub instrumental: usize,
+    pub not_found: usize,
+    pub pinned: usize,
+    pub expired: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheIndex {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// The UI and the fetch worker share the index file; one read-modify-write at a time.
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+fn index_lock() -> MutexGuard<'static, ()> {
+    INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+enum Lookup {
+    Fresh(LyricsData),
+    /// Past the TTL: refetch, but still better than nothing if that fails.
+    Stale(LyricsData),
+    NotFound,
+    Miss,
+}
 
-fn search(q: &str) -> Result<Vec<LrcLibResult>, LyricsError> {
-    let url = format!("https://lrclib.net/api/search?q={}", urlencoding::encode(q));
-    let resp = http().get(url).send()?.error_for_status()?;
-    Ok(resp.json()?)
+pub struct LyricsCache {
+    dir: PathBuf,
+    /// None keeps entries forever.
+    ttl: Option<chrono::Duration>,
+    negative_ttl: chrono::Duration,
+    /// 0 means unbounded. Pinned entries are never evicted.
+    max_entries: usize,
+    /// Add a romanized row to lyrics in kana, hangul or Cyrillic before they are stored.
+    romanize: bool,
 }
 
-pub fn fetch_lyrics(artist: &str, title: &str) -> Result<LyricsData, LyricsError> {
-    if let Some(cached) = load_cache(artist, title) {
-        log::debug!("Cache hit for {} - {}", artist, title);
-        return Ok(cached);
+impl LyricsCache {
+    pub fn new(cfg: &Config) -> Self {
+        Self {
+            dir: cache_dir(),
+            ttl: (cfg.cache_ttl_days > 0).then(|| chrono::Duration::days(cfg.cache_ttl_days as i64)),
+            negative_ttl: chrono::Duration::hours(cfg.cache_negative_ttl_hours as i64),
+            max_entries: cfg.cache_max_entries,
+            romanize: cfg.romanize,
+        }
     }
 
-    let res = search(&format!("{} {}", artist, title))
-        .or_else(|_| search(title))?;
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
 
-    if res.is_empty() {
-        return Err(LyricsError::NotFound);
+    /// The entry's lyrics file and the `.offset` saved next to it.
+    fn delete_files(&self, key: &str) {
+        let _ = std::fs::remove_file(self.entry_path(key));
+        let _ = std::fs::remove_file(self.dir.join(format!("{}.offset", key)));
     }
 
-    let best = &res[0];
-    log::debug!("Using lrclib result: {} - {}", best.artist, best.track);
+    /// An index from another layout version is dropped; its files are adopted one by
+    /// one as tracks are played again.
+    fn load_index(&self) -> CacheIndex {
+        let mut index: CacheIndex = std::fs::read_to_string(self.dir.join("index.json"))
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .filter(|i: &CacheIndex| i.version == CACHE_VERSION)
+            .unwrap_or_default();
+        index.version = CACHE_VERSION;
+        index
+    }
 
-    let lines = if let Some(ref p) = best.plain {
-        p.lines().map(|s| s.to_string()).collect()
-    } else if let Some(ref s) = best.synced {
-        s.lines()
-            .map(|l| {
-                if let Some(i) = l.find(']') {
-                    l[i + 1..].trim().to_string()
-                } else {
-                    l.to_string()
-                }
-            })
-            .collect()
-    } else {
-        vec![]
-    };
+    fn save_index(&self, index: &CacheIndex) {
+        let _ = std::fs::create_dir_all(&self.dir);
+        let tmp = self.dir.join("index.json.tmp");
+        if let Ok(json) = serde_json::to_string(index) {
+            if std::fs::write(&tmp, json).is_ok() {
+                let _ = std::fs::rename(tmp, self.dir.join("index.json"));
+            }
+        }
+    }
 
-    let synced = if let Some(ref s) = best.synced {
-        parse_lrc(s)
-    } else {
-        vec![]
-    };
+    fn read_entry(&self, key: &str) -> Option<LyricsData> {
+        read_cache_file(&self.entry_path(key))
+    }
+
+    fn write_entry(&self, key: &str, ld: &LyricsData) -> bool {
+        let _ = std::fs::create_dir_all(&self.dir);
+        let file = CacheFile { version: CACHE_VERSION, lyrics: ld.clone() };
+        serde_json::to_string(&file).is_ok_and(|json| std::fs::write(self.entry_path(key), json).is_ok())
+    }
+
+    /// Lyrics cached for the same track under another key: a different song id with
+    /// matching artist, title and duration, or a version 1 file. Copied to `key`.
+    fn adopt(&self, track: &TrackQuery, key: &str, index: &mut CacheIndex) -> Option<LyricsData> {
+        let other = index
+            .entries
+            .iter()
+            .filter(|(k, e)| k.as_str() != key && !e.not_found && same_track(e, track))
+            .max_by_key(|(_, e)| e.last_used)
+            .map(|(k, e)| (k.clone(), e.fetched_at));
+
+        let (ld, fetched_at) = match other.and_then(|(k, at)| Some((self.read_entry(&k)?, at, k))) {
+            Some((ld, at, k)) => {
+                log::info!("Cache: reusing {} for {}", k, key);
+                (ld, at)
+            }
+            None => {
+                let legacy = self.dir.join(format!("{}.json", legacy_key(&track.artist, &track.title)));
+                let ld = read_cache_file(&legacy)?;
+                log::info!("Cache: migrating {} to {}", legacy.display(), key);
+                let _ = std::fs::remove_file(legacy);
+                // listed by the cache view under its file name until now
+                index.entries.remove(&legacy_key(&track.artist, &track.title));
+                (ld, Utc::now())
+            }
+        };
+
+        if self.write_entry(key, &ld) {
+            let mut entry = new_entry(track, &ld, Utc::now());
+            entry.fetched_at = fetched_at;
+            index.entries.insert(key.to_string(), entry);
+        }
+        Some(ld)
+    }
+
+    fn lookup(&self, track: &TrackQuery) -> Lookup {
+        let _guard = index_lock();
+        let key = cache_key(track);
+        let mut index = self.load_index();
+        let now = Utc::now();
+
+        if let Some(e) = index.entries.get(&key).filter(|e| e.not_found) {
+            if now - e.fetched_at < self.negative_ttl {
+                return Lookup::NotFound;
+            }
+            index.entries.remove(&key);
+            self.save_index(&index);
+            return Lookup::Miss;
+        }
+
+        let Some(ld) = self.read_entry(&key).or_else(|| self.adopt(track, &key, &mut index)) else {
+            return Lookup::Miss;
+        };
+        // files written before the index existed get an entry on first use
+        let entry = index.entries.entry(key).or_insert_with(|| new_entry(track, &ld, now));
+        entry.last_used = now;
+        let stale = !ld.pinned && self.ttl.is_some_and(|ttl| now - entry.fetched_at > ttl);
+        self.save_index(&index);
+
+        if stale {
+            Lookup::Stale(ld)
+        } else {
+            Lookup::Fresh(ld)
+        }
+    }
+
+    pub(crate) fn store(&self, track: &TrackQuery, ld: &LyricsData) {
+        let _guard = index_lock();
+        let key = cache_key(track);
+        if !ld.pinned && self.read_entry(&key).is_some_and(|old| old.pinned) {
+            log::debug!("Keeping pinned lyrics for {} - {}", track.artist, track.title);
+            return;
+        }
+        if !self.write_entry(&key, ld) {
+            return;
+        }
+
+        let mut index = self.load_index();
+        index.entries.insert(key, new_entry(track, ld, Utc::now()));
+        self.evict(&mut index);
+        self.save_index(&index);
+    }
+
+    /// Remember that nobody had lyrics, so the providers aren't asked on every play.
+    pub(crate) fn store_not_found(&self, track: &TrackQuery) {
+        let _guard = index_lock();
+        let key = cache_key(track);
+        let mut index = self.load_index();
+        if index.entries.get(&key).is_some_and(|e| e.pinned) {
+            return;
+        }
+        // the lyrics went away; don't leave them on disk to be adopted again
+        let _ = std::fs::remove_file(self.entry_path(&key));
+        let now = Utc::now();
+        index.entries.insert(key, CacheEntry {
+            id: track.id.clone(),
+            artist: track.artist.clone(),
+            title: track.title.clone(),
+            album: track.album.clone(),
+            duration: track.duration,
+            provider: None,
+            source_id: None,
+            fetched_at: now,
+            last_used: now,
+            pinned: false,
+            not_found: true,
+            synced: false,
+            instrumental: false,
+        });
+        self.evict(&mut index);
+        self.save_index(&index);
+    }
+
+    // ---- management (cache view and `sonix_lyrics cache`) ----
+
+    /// Every entry, most recently used first.
+    pub fn entries(&self) -> Vec<(String, CacheEntry)> {
+        let _guard = index_lock();
+        let mut all: Vec<(String, CacheEntry)> = self.full_index().entries.into_iter().collect();
+        all.sort_by_key(|(_, e)| std::cmp::Reverse(e.last_used));
+        all
+    }
+
+    pub fn entry(&self, key: &str) -> Option<(CacheEntry, Option<LyricsData>)> {
+        let _guard = index_lock();
+        let e = self.full_index().entries.remove(key)?;
+        Some((e, self.read_entry(key)))
+    }
+
+    /// Delete an entry, pinned or not. False if there was no such key.
+    pub fn remove(&self, key: &str) -> bool {
+        let _guard = index_lock();
+        let mut index = self.load_index();
+        let found = index.entries.remove(key).is_some();
+        self.delete_files(key);
+        self.save_index(&index);
+        found
+    }
 
-    let ld = LyricsData { lines, synced };
-    save_cache(artist, title, &ld);
-    Ok(ld)
+    pub fn set_pinned(&self, key: &str, pinned: bool) -> bool {
+        let _guard = index_lock();
+        let mut index = self.full_index();
+        let Some(e) = index.entries.get_mut(key).filter(|e| !e.not_found) else { return false };
+        let Some(ld) = self.read_entry(key) else { return false };
+        if !self.write_entry(key, &LyricsData { pinned, ..ld }) {
+            return false;
+        }
+        e.pinned = pinned;
+        self.save_index(&index);
+        true
+    }
+
+    /// Drop expired entries and remembered misses, or with `all` everything but
+    /// pinned entries. Returns how many went.
+    pub fn purge(&self, all: bool) -> usize {
+        let _guard = index_lock();
+        let mut index = self.full_index();
+        let now = Utc::now();
+        let doomed: Vec<String> = index
+            .entries
+            .iter()
+            .filter(|(_, e)| !e.pinned && (all || e.not_found || self.expired(e, now)))
+            .map(|(k, _)| k.clone())
+            .collect();
+        for key in &doomed {
+            self.delete_files(key);
+            index.entries.remove(key);
+        }
+        self.save_index(&index);
+        doomed.len()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let _guard = index_lock();
+        let now = Utc::now();
+        let mut st = CacheStats::default();
+        for (key, e) in self.full_index().entries {
+            st.entries += 1;
+            if e.not_found {
+                st.not_found += 1;
+            } else if e.instrumental {
+                st.instrumental += 1;
+            } else if e.synced {
+                st.synced += 1;
+            } else {
+                st.plain += 1;
+            }
+            st.pinned += e.pinned as usize;
+            st.expired += (!e.pinned && self.expired(&e, now)) as usize;
+            for path in [self.entry_path(&key), self.dir.join(format!("{}.offset", key))] {
+                st.bytes += std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+            }
+        }
+        st
+    }
+
+    /// The index plus files it doesn't know about yet (written by older versions),
+    /// which are added under their file name until the track is played again.
+    fn full_index(&self) -> CacheIndex {
+        let mut index = self.load_index();
+        let Ok(dir) = std::fs::read_dir(&self.dir) else { return index };
+        let mut added = false;
+        for path in dir.flatten().map(|e| e.path()) {
+            let Some(key) = path.file_stem().and_then(|s| s.to_str()).map(str::to_string) else { continue };
+            if path.extension().is_none_or(|e| e != "json") || index.entries.contains_key(&key) {
+                continue;
+            }
+            let Some(ld) = read_cache_file(&path) else { continue };
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).map(DateTime::<Utc>::from);
+            let track = TrackQuery {
+                artist: ld.meta.artist.clone().unwrap_or_default(),
+                title: ld.meta.title.clone().unwrap_or_default(),
+                ..Default::default()
+            };
+            let mut entry = new_entry(&track, &ld, modified.unwrap_or_else(|_| Utc::now()));
+            entry.last_used = entry.fetched_at;
+            index.entries.insert(key, entry);
+            added = true;
+        }
+        if added {
+            self.save_index(&index);
+        }
+        index
+    }
+
+    fn expired(&self, e: &CacheEntry, now: DateTime<Utc>) -> bool {
+        if e.not_found {
+            now - e.fetched_at >= self.negative_ttl
+        } else {
+            self.ttl.is_some_and(|ttl| now - e.fetched_at > ttl)
+        }
+    }
+
+    /// Drop least recently used entries beyond `max_entries`.
+    fn evict(&self, index: &mut CacheIndex) {
+        if self.max_entries == 0 || index.entries.len() <= self.max_entries {
+            return;
+        }
+        let mut victims: Vec<(DateTime<Utc>, String)> = index
+            .entries
+            .iter()
+            .filter(|(_, e)| !e.pinned)
+            .map(|(k, e)| (e.last_used, k.clone()))
+            .collect();
+        victims.sort();
+
+        let excess = index.entries.len() - self.max_entries;
+        for (_, key) in victims.into_iter().take(excess) {
+            log::debug!("Evicting cached lyrics {}", key);
+            self.delete_files(&key);
+            index.entries.remove(&key);
+        }
+    }
+}
+
+/// Reads any layout up to the current one, rewriting older files in place.
+fn read_cache_file(path: &Path) -> Option<LyricsData> {
+    let data = std::fs::read_to_string(path).ok()?;
+    let file: CacheFile = serde_json::from_str(&data).ok()?;
+    if file.version > CACHE_VERSION {
+        log::warn!("{}: cache format {} is newer than this build understands", path.display(), file.version);
+        return None;
+    }
+    if file.version < CACHE_VERSION {
+        let upgraded = CacheFile { version: CACHE_VERSION, lyrics: file.lyrics };
+        if let Ok(json) = serde_json::to_string(&upgraded) {
+            let _ = std::fs::write(path, json);
+        }
+        return Some(upgraded.lyrics);
+    }
+    Some(file.lyrics)
+}
+
+/// Secondary lookup: same artist and title, durations within a couple of seconds.
+fn same_track(e: &CacheEntry, track: &TrackQuery) -> bool {
+    let close = e.duration == 0 || track.duration == 0 || e.duration.abs_diff(track.duration) <= 2;
+    close && e.artist.eq_ignore_ascii_case(&track.artist) && e.title.eq_ignore_ascii_case(&track.title)
+}
+
+fn new_entry(track: &TrackQuery, ld: &LyricsData, now: DateTime<Utc>) -> CacheEntry {
+    CacheEntry {
+        id: track.id.clone(),
+        artist: track.artist.clone(),
+        title: track.title.clone(),
+        album: track.album.clone(),
+        duration: track.duration,
+        provider: ld.source.as_ref().map(|s| s.provider.clone()),
+        source_id: ld.source.as_ref().and_then(|s| s.source_id.clone()),
+        fetched_at: now,
+        last_used: now,
+        pinned: ld.pinned,
+        not_found: false,
+        synced: !ld.synced.is_empty(),
+        instrumental: ld.instrumental,
+    }
+}
+
+// ---- per-track timing offset ----
+// Kept in a sidecar next to the cache entry so refetching lyrics doesn't lose it.
+
+fn offset_path(track: &TrackQuery) -> PathBuf {
+    cache_dir().join(format!("{}.offset", cache_key(track)))
+}
+
+/// User timing adjustment for a track in milliseconds (positive = lyrics sooner).
+/// Kept beside the track's cache entry and deleted with it.
+pub fn load_offset(track: &TrackQuery) -> i64 {
+    let legacy = cache_dir().join(format!("{}.offset", legacy_key(&track.artist, &track.title)));
+    std::fs::read_to_string(offset_path(track))
+        .or_else(|_| std::fs::read_to_string(legacy))
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+pub fn save_offset(track: &TrackQuery, offset_ms: i64) {
+    let path = offset_path(track);
+    let _ = std::fs::remove_file(cache_dir().join(format!("{}.offset", legacy_key(&track.artist, &track.title))));
+    if offset_ms == 0 {
+        let _ = std::fs::remove_file(path);
+        return;
+    }
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let _ = std::fs::write(path, offset_ms.to_string());
+}
+
+/// Store hand-picked lyrics for a track and pin them against automatic lookups.
+pub fn pin_lyrics(cache: &LyricsCache, track: &TrackQuery, ld: &LyricsData) -> LyricsData {
+    let pinned = romanized(LyricsData { pinned: true, ..ld.clone() }, cache.romanize);
+    cache.store(track, &pinned);
+    pinned
+}
+
+/// Hand-timed lines from the timing editor: written as LRC into the local lyrics
+/// folder (at `LOCAL_LYRICS_TEMPLATE` if set, else `Artist - Title.lrc`) and pinned
+/// in the cache so nothing fetched later replaces them.
+pub fn save_timed_lyrics(
+    cfg: &Config,
+    cache: &LyricsCache,
+    track: &TrackQuery,
+    mut synced: Vec<SyncedLine>,
+) -> Result<(PathBuf, LyricsData), LyricsError> {
+    synced.sort_by_key(|l| l.time_ms);
+    fill_word_ends(&mut synced, (track.duration > 0).then_some(track.duration * 1000));
+
+    let path = LocalLyrics::new(cfg).save_path(track);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let ld = LyricsData {
+        lines: synced.iter().map(|l| l.text.clone()).collect(),
+        synced,
+        source: Some(Provenance { provider: "local".into(), source_id: Some(path.display().to_string()) }),
+        ..Default::default()
+    };
+    export_lyrics(&path, &ld, track, 0)?;
+    Ok((path, pin_lyrics(cache, track, &ld)))
+}
+
+// ---- export ----
+
+/// Write synced lyrics to `path` as LRC, SRT or WebVTT, picked by its extension.
+/// The per-track `offset_ms` is baked into the timestamps. Each cue ends where the
+/// next line starts; the last one at the end of the track.
+pub fn export_lyrics(path: &Path, ld: &LyricsData, track: &TrackQuery, offset_ms: i64) -> Result<(), LyricsError> {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+    let format = LyricsFormat::from_extension(ext)
+        .filter(|f| *f != LyricsFormat::Ttml)
+        .ok_or_else(|| LyricsError::ExportFormat(ext.to_string()))?;
+    let text = render_export(ld, track, offset_ms, format)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, text)?;
+    Ok(())
+}
+
+/// Default export file name, e.g. `Artist - Title.srt`.
+pub fn export_file_name(track: &TrackQuery, ext: &str) -> String {
+    path_safe(&format!("{} - {}.{}", track.artist, track.title, ext))
+}
+
+fn render_export(ld: &LyricsData, track: &TrackQuery, offset_ms: i64, format: LyricsFormat) -> Result<String, LyricsError> {
+    if ld.synced.is_empty() {
+        return Err(LyricsError::NotSynced);
+    }
+    let lines: Vec<SyncedLine> = ld.synced.iter().cloned().map(|mut l| {
+        l.time_ms = shift_ms(l.time_ms, offset_ms);
+        for w in &mut l.words {
+            w.shift(offset_ms);
+        }
+        l
+    }).collect();
+    let end_ms = match (track.duration, ld.meta.length_ms) {
+        (d, _) if d > 0 => d * 1000,
+        (_, Some(len)) => len,
+        _ => 0,
+    };
+
+    Ok(match format {
+        LyricsFormat::Srt | LyricsFormat::Vtt => export_cues(&lines, end_ms, format == LyricsFormat::Vtt),
+        _ => export_lrc(&lines, track, end_ms),
+    })
+}
+
+fn export_lrc(lines: &[SyncedLine], track: &TrackQuery, end_ms: u32) -> String {
+    let mut out = String::new();
+    for (tag, value) in [("ar", &track.artist), ("ti", &track.title), ("al", &track.album)] {
+        if !value.is_empty() {
+            out.push_str(&format!("[{}:{}]\n", tag, value));
+        }
+    }
+    if end_ms > 0 {
+        out.push_str(&format!("[length:{:02}:{:02}]\n", end_ms / 60_000, end_ms / 1000 % 60));
+    }
+
+    for (i, l) in lines.iter().enumerate() {
+        out.push_str(&format!("[{}]", lrc_ts(l.time_ms)));
+        if l.words.is_empty() {
+            out.push_str(&l.text);
+        }
+        for w in &l.words {
+            out.push_str(&format!("<{}>{}", lrc_ts(w.time_ms), w.word));
+        }
+        // a trailing tag only when the last word stops before the next line
+        let next = lines.get(i + 1).map(|n| n.time_ms);
+        if let Some(last) = l.words.last().filter(|w| w.end_ms > w.time_ms && Some(w.end_ms) != next) {
+            out.push_str(&format!("<{}>", lrc_ts(last.end_ms)));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// SRT, or WebVTT with inline `<hh:mm:ss.mmm>` word timestamps when `vtt`.
+fn export_cues(lines: &[SyncedLine], end_ms: u32, vtt: bool) -> String {
+    let mut out = String::from(if vtt { "WEBVTT\n\n" } else { "" });
+    let mut n = 0;
+    for (i, l) in lines.iter().enumerate() {
+        if l.text.trim().is_empty() {
+            continue;
+        }
+        let start = l.time_ms;
+        let end = lines.get(i + 1).map(|n| n.time_ms).unwrap_or(end_ms);
+        let end = if end > start { end } else { start + LAST_WORD_MS };
+
+        n += 1;
+        let sep = if vtt { '.' } else { ',' };
+        if !vtt {
+            out.push_str(&format!("{}\n", n));
+        }
+        out.push_str(&format!("{} --> {}\n", cue_ts(start, sep), cue_ts(end, sep)));
+
+        if vtt && !l.words.is_empty() {
+            for w in &l.words {
+                if w.time_ms > start && w.time_ms < end {
+                    out.push_str(&format!("<{}>", cue_ts(w.time_ms, '.')));
+                }
+                out.push_str(&w.word);
+            }
+            out.push('\n');
+        } else {
+            out.push_str(l.text.trim());
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn lrc_ts(ms: u32) -> String {
+    format!("{:02}:{:02}.{:02}", ms / 60_000, ms / 1000 % 60, ms % 1000 / 10)
+}
+
+fn cue_ts(ms: u32, sep: char) -> String {
+    format!("{:02}:{:02}:{:02}{}{:03}", ms / 3_600_000, ms / 60_000 % 60, ms / 1000 % 60, sep, ms % 1000)
+}
+
+// ---- providers ----
+
+/// Build the provider chain from `LYRICS_PROVIDERS`, in order. Unknown names are skipped.
+pub fn providers_from_config(cfg: &Config) -> Vec<Box<dyn LyricsProvider>> {
+    let mut out: Vec<Box<dyn LyricsProvider>> = Vec::new();
+    for name in &cfg.lyrics_providers {
+        match name.as_str() {
+            "local" => out.push(Box::new(LocalLyrics::new(cfg))),
+            "navidrome" => out.push(Box::new(NavidromeLyrics::new(cfg))),
+            "lrclib" => out.push(Box::new(LrcLib::new(&cfg.lrclib_url))),
+            other => log::warn!("Unknown lyrics provider '{}' in LYRICS_PROVIDERS", other),
+        }
+    }
+    out
+}
+
+pub fn fetch_lyrics(
+    providers: &[Box<dyn LyricsProvider>],
+    cache: &LyricsCache,
+    track: &TrackQuery,
+) -> Result<LyricsData, LyricsError> {
+    match lookup_lyrics(providers, cache, track) {
+        // only a guess, so it's never cached: the providers are asked again next time
+        Err(LyricsError::NotFound) if instrumental_title(&track.title) => {
+            log::info!("No lyrics for {} - {}, the title says instrumental", track.artist, track.title);
+            Ok(LyricsData {
+                instrumental: true,
+                source: Some(Provenance { provider: "title".into(), source_id: None }),
+                ..Default::default()
+            })
+        }
+        // cache hits from before ROMANIZE was switched on or off are adjusted too
+        res => res.map(|ld| romanized(ld, cache.romanize)),
+    }
+}
+
+fn lookup_lyrics(
+    providers: &[Box<dyn LyricsProvider>],
+    cache: &LyricsCache,
+    track: &TrackQuery,
+) -> Result<LyricsData, LyricsError> {
+    let cached = cache.lookup(track);
+    if let Lookup::Fresh(ref c) = cached {
+        if c.pinned {
+            log::debug!("Pinned lyrics for {} - {}", track.artist, track.title);
+            return Ok(c.clone());
+        }
+    }
+
+    for p in providers.iter().filter(|p| p.is_local()) {
+        match p.fetch(track) {
+            Ok(ld) => {
+                log::info!("Lyrics for {} - {} from {}", track.artist, track.title, p.name());
+                return Ok(ld);
+            }
+            Err(LyricsError::NotFound) => log::debug!("{}: no lyrics", p.name()),
+            Err(e) => log::warn!("{}: {}", p.name(), e),
+        }
+    }
+
+    let stale = match cached {
+        Lookup::Fresh(ld) => {
+            log::debug!("Cache hit for {} - {}", track.artist, track.title);
+            return Ok(ld);
+        }
+        Lookup::NotFound => {
+            log::debug!("Cached miss for {} - {}", track.artist, track.title);
+            return Err(LyricsError::NotFound);
+        }
+        Lookup::Stale(ld) => Some(ld),
+        Lookup::Miss => None,
+    };
+
+    if track.instrumental {
+        log::info!("{} - {} is tagged instrumental, not looking up lyrics", track.artist, track.title);
+        let ld = LyricsData {
+            instrumental: true,
+            source: Some(Provenance { provider: "tags".into(), source_id: None }),
+            ..Default::default()
+        };
+        cache.store(track, &ld);
+        return Ok(ld);
+    }
+
+    let mut last_err = LyricsError::NotFound;
+    let mut suspect: Option<LyricsData> = None;
+    for fallback in [false, true] {
+        for p in providers.iter().filter(|p| !p.is_local()) {
+            let res = if fallback { p.fallback(track) } else { p.fetch(track) };
+            match res {
+                Ok(ld) => {
+                    let ld = romanized(ld, cache.romanize);
+                    if let Some(why) = metadata_mismatch(&ld.meta, track) {
+                        log::warn!("{}: lyrics look like another track ({}), trying next", p.name(), why);
+                        suspect.get_or_insert(ld);
+                        continue;
+                    }
+                    log::info!("Lyrics for {} - {} from {}", track.artist, track.title, p.name());
+                    cache.store(track, &ld);
+                    return Ok(ld);
+                }
+                Err(LyricsError::NotFound) => log::debug!("{}: no lyrics", p.name()),
+                Err(e) => {
+                    log::warn!("{}: {}", p.name(), e);
+                    last_err = e;
+                }
+            }
+        }
+    }
+
+    // nothing better turned up; a doubtful match beats none, but isn't kept
+    // so the next play looks again
+    if let Some(ld) = suspect {
+        return Ok(ld);
+    }
+    if let Some(ld) = stale {
+        log::info!("Refetch failed, keeping expired lyrics for {} - {}", track.artist, track.title);
+        return Ok(ld);
+    }
+    // only a clean "nobody has it" is remembered, not network trouble
+    if matches!(last_err, LyricsError::NotFound) {
+        cache.store_not_found(track);
+    }
+    Err(last_err)
+}
+
+/// Free-text search across every provider that supports it, ranked against `track`.
+pub fn search_candidates(
+    providers: &[Box<dyn LyricsProvider>],
+    track: &TrackQuery,
+    query: &str,
+) -> Result<Vec<(f32, Candidate)>, LyricsError> {
+    let mut all = Vec::new();
+    let mut last_err = None;
+    for p in providers {
+        match p.search(query) {
+            Ok(mut hits) => all.append(&mut hits),
+            Err(e) => {
+                log::warn!("{} search: {}", p.name(), e);
+                last_err = Some(e);
+            }
+        }
+    }
+    match last_err {
+        Some(e) if all.is_empty() => Err(e),
+        _ => Ok(rank_candidates(track, all)),
+    }
+}
+
+// ---- local files ----
+
+/// `.lrc`, `.ttml`, `.vtt`, `.srt` or `.txt` files in `LOCAL_LYRICS_DIR`, matched by `Artist - Title`, by
+/// Navidrome song id, or by `LOCAL_LYRICS_TEMPLATE`. The directory is read on
+/// every lookup, so new files are picked up without a restart.
+pub struct LocalLyrics {
+    dir: PathBuf,
+    template: String,
+}
+
+/// In order of preference when several files match the same track.
+const LOCAL_EXTENSIONS: [&str; 5] = ["lrc", "ttml", "vtt", "srt", "txt"];
+
+impl LocalLyrics {
+    pub fn new(cfg: &Config) -> Self {
+        Self { dir: cfg.local_lyrics_path(), template: cfg.local_lyrics_template.clone() }
+    }
+
+    fn find(&self, track: &TrackQuery) -> Option<PathBuf> {
+        if !self.template.is_empty() {
+            let rel = self.template_rel(track);
+            for ext in LOCAL_EXTENSIONS {
+                let p = self.dir.join(format!("{}.{}", rel, ext));
+                if p.is_file() {
+                    return Some(p);
+                }
+            }
+        }
+
+        let wanted = normalize(&format!("{} - {}", track.artist, track.title));
+        let mut best: Option<(usize, PathBuf)> = None;
+        for entry in std::fs::read_dir(&self.dir).ok()?.flatten() {
+            let path = entry.path();
+            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_ascii_lowercase();
+            let Some(rank) = LOCAL_EXTENSIONS.iter().position(|e| *e == ext) else { continue };
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+            let hit = (!track.id.is_empty() && stem == track.id) || normalize(stem) == wanted;
+            if hit && best.as_ref().is_none_or(|(r, _)| rank < *r) {
+                best = Some((rank, path));
+            }
+        }
+        best.map(|(_, path)| path)
+    }
+
+    /// Where a new `.lrc` for the track goes: the template path if one is set.
+    fn save_path(&self, track: &TrackQuery) -> PathBuf {
+        if self.template.is_empty() {
+            return self.dir.join(export_file_name(track, "lrc"));
+        }
+        self.dir.join(format!("{}.lrc", self.template_rel(track)))
+    }
+
+    fn template_rel(&self, track: &TrackQuery) -> String {
+        self.template
+            .replace("{artist}", &path_safe(&track.artist))
+            .replace("{album}", &path_safe(&track.album))
+            .replace("{title}", &path_safe(&track.title))
+            .replace("{id}", &path_safe(&track.id))
+    }
+
+    /// `Song.en.lrc`, `Song.romaji.lrc`… next to `Song.lrc`, labelled by the middle part.
+    fn find_parallel(&self, main: &Path) -> Vec<(String, PathBuf)> {
+        let (Some(dir), Some(stem)) = (main.parent(), main.file_stem().and_then(|s| s.to_str())) else {
+            return vec![];
+        };
+        let Ok(entries) = std::fs::read_dir(dir) else { return vec![] };
+        let mut out: Vec<(String, PathBuf)> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter_map(|p| {
+                let ext = p.extension()?.to_str()?.to_ascii_lowercase();
+                let label = p.file_stem()?.to_str()?.strip_prefix(stem)?.strip_prefix('.')?.to_string();
+                (LOCAL_EXTENSIONS.contains(&ext.as_str()) && ext != "txt" && !label.is_empty()).then_some((label, p))
+            })
+            .collect();
+        out.sort();
+        out
+    }
+}
+
+impl LyricsProvider for LocalLyrics {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    fn fetch(&self, track: &TrackQuery) -> Result<LyricsData, LyricsError> {
+        let path = self.find(track).ok_or(LyricsError::NotFound)?;
+        log::debug!("Local lyrics file: {}", path.display());
+        let text = std::fs::read_to_string(&path).map_err(|_| LyricsError::NotFound)?;
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+        let mut ld = lyrics_from_text(&text, LyricsFormat::from_extension(ext));
+        if !ld.synced.is_empty() {
+            for (label, p) in self.find_parallel(&path) {
+                let Ok(text) = std::fs::read_to_string(&p) else { continue };
+                let ext = p.extension().and_then(|e| e.to_str()).unwrap_or_default();
+                let other = lyrics_from_text(&text, LyricsFormat::from_extension(ext));
+                match align_parallel(&ld.synced, &other.synced, &label) {
+                    Some(pl) => ld.parallel.push(pl),
+                    None => log::warn!("{}: no lines line up with {}", p.display(), path.display()),
+                }
+            }
+        }
+        ld.source = Some(Provenance {
+            provider: "local".into(),
+            source_id: Some(path.display().to_string()),
+        });
+        Ok(ld)
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+}
+
+/// Synced lyrics in `format` (sniffed when unknown) if the text has timestamps,
+/// plain lines otherwise.
+fn lyrics_from_text(text: &str, format: Option<LyricsFormat>) -> LyricsData {
+    let (mut synced, meta) = parse_lyrics(text, format.unwrap_or_else(|| LyricsFormat::sniff(text)));
+    let parallel = split_parallel(&mut synced);
+    let lines = if synced.is_empty() {
+        text.lines().map(|l| l.trim_end().to_string()).collect()
+    } else {
+        synced.iter().map(|l| l.text.clone()).collect()
+    };
+    LyricsData { lines, synced, meta, parallel, ..Default::default() }
+}
+
+// ---- parallel lines ----
+
+/// Takes lines that repeat an earlier line's timestamp out of `synced`: the first
+/// line at a time is the original, the 2nd, 3rd… become parallel rows in order.
+fn split_parallel(synced: &mut Vec<SyncedLine>) -> Vec<ParallelLines> {
+    let mut main: Vec<SyncedLine> = Vec::with_capacity(synced.len());
+    let mut extra: Vec<Vec<(usize, String)>> = Vec::new();
+    let mut depth = 0;
+    for line in synced.drain(..) {
+        if main.last().is_some_and(|m| m.time_ms == line.time_ms) {
+            if extra.len() <= depth {
+                extra.push(Vec::new());
+            }
+            extra[depth].push((main.len() - 1, line.text));
+            depth += 1;
+        } else {
+            main.push(line);
+            depth = 0;
+        }
+    }
+    *synced = main;
+
+    extra
+        .into_iter()
+        .map(|rows| {
+            let mut lines = vec![String::new(); synced.len()];
+            for (i, text) in rows {
+                lines[i] = text;
+            }
+            ParallelLines { label: "translation".into(), lines }
+        })
+        .collect()
+}
+
+const ROMANIZED: &str = "romanized";
+
+/// With `on`, adds a `romanized` row when the lyrics have kana, hangul or Cyrillic
+/// and no romanization of their own; without it, drops one added earlier.
+fn romanized(mut ld: LyricsData, on: bool) -> LyricsData {
+    if !on {
+        ld.parallel.retain(|p| p.label != ROMANIZED);
+        return ld;
+    }
+    if ld.synced.is_empty() || ld.parallel.iter().any(|p| p.label.starts_with("roma")) {
+        return ld;
+    }
+    let lines: Vec<String> = ld.synced.iter().map(|l| romanize(&l.text).unwrap_or_default()).collect();
+    if lines.iter().any(|l| !l.is_empty()) {
+        ld.parallel.push(ParallelLines { label: ROMANIZED.into(), lines });
+    }
+    ld
+}
+
+/// Lines from a second file lined up with `main` by timestamp (nearest within
+/// `ALIGN_MS`). None if no line matched at all.
+fn align_parallel(main: &[SyncedLine], other: &[SyncedLine], label: &str) -> Option<ParallelLines> {
+    const ALIGN_MS: u32 = 500;
+    let lines: Vec<String> = main
+        .iter()
+        .map(|m| {
+            let at = other.partition_point(|o| o.time_ms < m.time_ms);
+            [at.checked_sub(1), Some(at)]
+                .into_iter()
+                .flatten()
+                .filter_map(|j| other.get(j))
+                .filter(|o| o.time_ms.abs_diff(m.time_ms) <= ALIGN_MS)
+                .min_by_key(|o| o.time_ms.abs_diff(m.time_ms))
+                .map(|o| o.text.clone())
+                .unwrap_or_default()
+        })
+        .collect();
+    lines.iter().any(|l| !l.is_empty()).then(|| ParallelLines { label: label.to_string(), lines })
+}
+
+fn path_safe(s: &str) -> String {
+    s.replace(['/', '\\'], "_")
+}
+
+// ---- navidrome ----
+
+/// Lyrics served by Navidrome itself: OpenSubsonic `getLyricsBySongId` when the
+/// server advertises `songLyrics`, otherwise legacy `getLyrics` as a fallback.
+pub struct NavidromeLyrics {
+    cfg: Config,
+    song_lyrics: OnceLock<bool>,
+}
+
+impl NavidromeLyrics {
+    pub fn new(cfg: &Config) -> Self {
+        Self { cfg: cfg.clone(), song_lyrics: OnceLock::new() }
+    }
+
+    fn supports_song_lyrics(&self) -> bool {
+        *self.song_lyrics.get_or_init(|| {
+            match navidrome::get_open_subsonic_extensions(&self.cfg) {
+                Ok(ext) => ext.iter().any(|e| e == "songLyrics"),
+                Err(e) => {
+                    log::warn!("getOpenSubsonicExtensions: {}", e);
+                    false
+                }
+            }
+        })
+    }
+}
+
+impl LyricsProvider for NavidromeLyrics {
+    fn name(&self) -> &'static str {
+        "navidrome"
+    }
+
+    fn fetch(&self, track: &TrackQuery) -> Result<LyricsData, LyricsError> {
+        if track.id.is_empty() || !self.supports_song_lyrics() {
+            return Err(LyricsError::NotFound);
+        }
+
+        let mut all = navidrome::get_lyrics_by_song_id(&self.cfg, &track.id)?;
+        let pick = all.iter().position(|l| l.synced).unwrap_or(0);
+        if pick >= all.len() {
+            return Err(LyricsError::NotFound);
+        }
+        let main = all.remove(pick);
+        let lang = main.lang.clone();
+        let mut ld = structured_to_lyrics(main, &track.id);
+        ld.parallel.extend(other_languages(&ld.synced, &lang, all, &track.id));
+        Ok(ld)
+    }
+
+    fn fallback(&self, track: &TrackQuery) -> Result<LyricsData, LyricsError> {
+        let mut text = navidrome::get_lyrics(&self.cfg, &track.artist, &track.title)?;
+        let (artist, title) = (primary_artist(&track.artist), clean_title(&track.title));
+        if text.is_none() && (artist != track.artist || title != track.title) {
+            log::info!("navidrome getLyrics: retrying as \"{}\" / \"{}\"", artist, title);
+            text = navidrome::get_lyrics(&self.cfg, &artist, &title)?;
+        }
+        let text = text.ok_or(LyricsError::NotFound)?;
+        // usually plain text, but some servers pass sidecar files through as-is
+        let mut ld = lyrics_from_text(&text, None);
+        ld.source = Some(Provenance { provider: "navidrome".into(), source_id: None });
+        Ok(ld)
+    }
+}
+
+/// The other synced entries of a song, as parallel rows: usually translations of the
+/// same lines. Another copy in the chosen language, or one whose lines are the
+/// original's, adds nothing.
+fn other_languages(synced: &[SyncedLine], lang: &str, others: Vec<StructuredLyrics>, song_id: &str) -> Vec<ParallelLines> {
+    let unknown = |l: &str| l.is_empty() || l == "xxx";
+    let original: Vec<&str> = synced.iter().map(|l| l.text.as_str()).collect();
+    others
+        .into_iter()
+        .filter(|l| l.synced && (l.lang != lang || unknown(lang)))
+        .filter_map(|other| {
+            let label = if unknown(&other.lang) { "translation".to_string() } else { other.lang.clone() };
+            align_parallel(synced, &structured_to_lyrics(other, song_id).synced, &label)
+        })
+        .filter(|p| p.lines.iter().map(String::as_str).ne(original.iter().copied()))
+        .collect()
+}
+
+fn structured_to_lyrics(sl: StructuredLyrics, song_id: &str) -> LyricsData {
+    log::debug!("Navidrome lyrics: lang={} synced={} offset={}", sl.lang, sl.synced, sl.offset_ms);
+
+    let mut synced: Vec<SyncedLine> = if sl.synced {
+        sl.lines
+            .iter()
+            .filter_map(|l| {
+                let start = l.start_ms?;
+                let mut words = parse_karaoke_words(&l.value, start, false);
+                for w in &mut words {
+                    w.shift(sl.offset_ms);
+                }
+                Some(SyncedLine {
+                    time_ms: shift_ms(start, sl.offset_ms),
+                    text: strip_karaoke_tags(&l.value, false),
+                    words,
+                })
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+    synced.sort_by_key(|l| l.time_ms);
+    fill_word_ends(&mut synced, None);
+    let parallel = split_parallel(&mut synced);
+    // translations sharing a timestamp are parallel rows, not lines of their own
+    let lines = if synced.is_empty() {
+        sl.lines.iter().map(|l| strip_karaoke_tags(&l.value, false)).collect()
+    } else {
+        synced.iter().map(|l| l.text.clone()).collect()
+    };
+
+    LyricsData {
+        lines,
+        synced,
+        parallel,
+        source: Some(Provenance { provider: "navidrome".into(), source_id: Some(song_id.to_string()) }),
+        ..Default::default()
+    }
+}
+
+// ---- lrclib ----
+
+pub struct LrcLib {
+    base_url: String,
+}
+
+impl LrcLib {
+    pub fn new(base_url: &str) -> Self {
+        Self { base_url: base_url.trim_end_matches('/').to_string() }
+    }
+
+    fn api_search(&self, q: &str) -> Result<Vec<LrcLibResult>, LyricsError> {
+        let url = format!("{}/api/search?q={}", self.base_url, urlencoding::encode(q));
+        let resp = http().get(url).send()?.error_for_status()?;
+        Ok(resp.json()?)
+    }
+
+    /// `/api/get` only answers when track, artist, album and duration (±2s) all match.
+    fn api_get(&self, track: &TrackQuery) -> Result<Option<LrcLibResult>, LyricsError> {
+        let url = format!(
+            "{}/api/get?track_name={}&artist_name={}&album_name={}&duration={}",
+            self.base_url,
+            urlencoding::encode(&track.title),
+            urlencoding::encode(&track.artist),
+            urlencoding::encode(&track.album),
+            track.duration
+        );
+        let resp = http().get(url).send()?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(resp.error_for_status()?.json()?))
+    }
+}
+
+impl LyricsProvider for LrcLib {
+    fn name(&self) -> &'static str {
+        "lrclib"
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Candidate>, LyricsError> {
+        Ok(self.api_search(query)?.into_iter().map(LrcLibResult::into_candidate).collect())
+    }
+
+    /// Walks `query_ladder` until an attempt gives an exact hit or a good match,
+    /// otherwise settles for the best candidate seen above `MIN_MATCH`.
+    fn fetch(&self, track: &TrackQuery) -> Result<LyricsData, LyricsError> {
+        let mut best: Option<(f32, Candidate)> = None;
+        let mut last_err = None;
+
+        for (n, attempt) in query_ladder(track).into_iter().enumerate() {
+            let found = match attempt {
+                Attempt::Exact(ref q) => self.api_get(q).map(|hit| hit.map(LrcLibResult::into_candidate).into_iter().collect()),
+                Attempt::Search(ref q) => self.search(q),
+            };
+            let ranked = match found {
+                Ok(candidates) => rank_candidates(track, candidates),
+                Err(e) => {
+                    log::warn!("lrclib attempt {} ({}): {}", n + 1, attempt, e);
+                    last_err = Some(e);
+                    continue;
+                }
+            };
+
+            let Some((score, top)) = ranked.into_iter().next() else {
+                log::info!("lrclib attempt {} ({}): no results", n + 1, attempt);
+                continue;
+            };
+            log::info!(
+                "lrclib attempt {} ({}): {} - {} ({}, {}s) score {:.2}",
+                n + 1, attempt, top.artist, top.title, top.album, top.duration, score
+            );
+            // /api/get only answers for the same recording
+            if matches!(attempt, Attempt::Exact(_)) || score >= GOOD_MATCH {
+                return Ok(top.lyrics);
+            }
+            if score < MIN_MATCH {
+                log::info!("lrclib attempt {} ({}): best score below {:.2}, ignored", n + 1, attempt, MIN_MATCH);
+                continue;
+            }
+            if best.as_ref().is_none_or(|(b, _)| score > *b) {
+                best = Some((score, top));
+            }
+        }
+
+        match best {
+            Some((score, c)) => {
+                log::info!("Using best lrclib result: {} - {} score {:.2}", c.artist, c.title, score);
+                Ok(c.lyrics)
+            }
+            None => Err(last_err.unwrap_or(LyricsError::NotFound)),
+        }
+    }
+}
+
+/// A candidate scoring this well ends the lookup ladder early.
+const GOOD_MATCH: f32 = 0.75;
+/// Search results scoring below this are someone else's song; better no lyrics.
+const MIN_MATCH: f32 = 0.5;
+
+enum Attempt {
+    /// `/api/get` with these exact fields.
+    Exact(TrackQuery),
+    /// `/api/search?q=`.
+    Search(String),
+}
+
+impl std::fmt::Display for Attempt {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Attempt::Exact(q) => write!(f, "get \"{}\" / \"{}\" / \"{}\" / {}s", q.artist, q.title, q.album, q.duration),
+            Attempt::Search(q) => write!(f, "search \"{}\"", q),
+        }
+    }
+}
+
+/// Lookups from strictest to loosest: the exact fields, then with version notes and
+/// featured artists dropped, then free-text searches getting looser each step.
+fn query_ladder(track: &TrackQuery) -> Vec<Attempt> {
+    let title = clean_title(&track.title);
+    let (artist, featured) = split_artists(&track.artist);
+    if !featured.is_empty() {
+        log::debug!("{}: featuring {}", track.artist, featured.join(", "));
+    }
+
+    let mut out = Vec::new();
+    if track.duration > 0 && !track.album.is_empty() {
+        out.push(Attempt::Exact(track.clone()));
+        if title != track.title || artist != track.artist {
+            let album = clean_title(&track.album);
+            out.push(Attempt::Exact(TrackQuery { artist: artist.clone(), title: title.clone(), album, ..track.clone() }));
+        }
+    }
+
+    let searches = [
+        format!("{} {}", track.artist, track.title),
+        format!("{} {}", artist, title),
+        format!("{} {}", normalize(&artist), normalize(&title)),
+        normalize(&title),
+    ];
+    for q in searches {
+        let q = q.trim().to_string();
+        if !q.is_empty() && !out.iter().any(|a| matches!(a, Attempt::Search(s) if *s == q)) {
+            out.push(Attempt::Search(q));
+        }
+    }
+    out
+}
+
+// ---- title / artist normalization ----
+
+/// Words that mark a bracketed or dashed title suffix as a release note rather than
+/// part of the name. "Instrumental" and "karaoke" stay: those really have no lyrics.
+const VERSION_WORDS: [&str; 16] = [
+    "remaster", "remastered", "live", "edition", "version", "mono", "stereo", "deluxe",
+    "bonus", "demo", "edit", "mix", "remix", "explicit", "anniversary", "single",
+];
+
+const FEAT_WORDS: [&str; 4] = ["feat", "feat.", "ft.", "featuring"];
+
+/// "Song (2011 Remaster)", "Song - Live at X", "Song [Deluxe Edition]" and
+/// "Song (feat. X)" all become "Song".
+fn clean_title(title: &str) -> String {
+    let mut t = title.trim().to_string();
+    loop {
+        let before = t.len();
+        if t.ends_with([')', ']']) {
+            if let Some(open) = t.rfind(['(', '[']) {
+                if is_version_note(&t[open + 1..t.len() - 1]) {
+                    t.truncate(open);
+                }
+            }
+        }
+        if let Some((head, tail)) = [" - ", " – ", " — "].iter().filter_map(|sep| t.rsplit_once(sep)).next() {
+            if is_version_note(tail) {
+                t = head.to_string();
+            }
+        }
+        t = t.trim_end().to_string();
+        if t.len() == before {
+            break;
+        }
+    }
+    if let Some(at) = find_feat(&t) {
+        t.truncate(at);
+    }
+    let t = t.trim();
+    if t.is_empty() { title.trim().to_string() } else { t.to_string() }
+}
+
+fn is_version_note(s: &str) -> bool {
+    let lower = s.to_lowercase();
+    lower.split_whitespace().next().is_some_and(|w| FEAT_WORDS.contains(&w) || w == "ft")
+        || normalize(s)
+            .split(' ')
+            .any(|w| VERSION_WORDS.contains(&w) || w.starts_with("remaster"))
+}
+
+/// Byte offset of a " feat. " / " ft. " / " featuring " marker, ASCII case-insensitive.
+fn find_feat(s: &str) -> Option<usize> {
+    let lower = s.to_ascii_lowercase();
+    [" feat. ", " feat ", " ft. ", " ft ", " featuring ", " (feat", " [feat", " (ft", " [ft"]
+        .iter()
+        .filter_map(|m| lower.find(m))
+        .min()
+}
+
+/// "Artist feat. Other & Third" → ("Artist", ["Other", "Third"]). Multi-artist tags
+/// joined with ";", "•" or " / " are split too; "&" and a bare "/" are left alone
+/// (Simon & Garfunkel, AC/DC).
+fn split_artists(artist: &str) -> (String, Vec<String>) {
+    let (main, feat) = match find_feat(artist) {
+        Some(at) => (&artist[..at], &artist[at..]),
+        None => (artist, ""),
+    };
+    let main = main.replace(" / ", ";");
+    let mut parts = main.split([';', '•']).map(str::trim).filter(|p| !p.is_empty());
+    let primary = parts.next().unwrap_or(artist.trim()).to_string();
+
+    let feat = feat.trim().trim_start_matches(['(', '[']).trim_end_matches([')', ']']);
+    let feat = feat.split_once(' ').map(|(_, rest)| rest).unwrap_or("");
+    let featured = parts
+        .map(str::to_string)
+        .chain(feat.split([',', '&']).map(|p| p.trim().to_string()).filter(|p| !p.is_empty()))
+        .collect();
+    (primary, featured)
+}
+
+/// A title like "Song (Instrumental)" / "Song - Off Vocal". Providers sometimes have
+/// lyrics for these anyway (a vocal sample, a mislabeled file), so this only settles
+/// a lookup that found nothing.
+fn instrumental_title(title: &str) -> bool {
+    let title = normalize(title);
+    title != "instrumental" && (title.split(' ').any(|w| w == "instrumental") || title.contains("off vocal"))
+}
+
+fn primary_artist(artist: &str) -> String {
+    split_artists(artist).0
+}
+
+/// Lowercase ASCII-ish form: diacritics folded ("Beyoncé" → "beyonce"), apostrophes
+/// dropped ("Don't" → "dont"), "&" read as "and", other punctuation as spaces.
+fn normalize(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars().flat_map(char::to_lowercase) {
+        match c {
+            '\'' | '’' | '`' => {}
+            '&' => out.push_str(" and "),
+            'ø' => out.push('o'),
+            'æ' => out.push_str("ae"),
+            'œ' => out.push_str("oe"),
+            'ß' => out.push_str("ss"),
+            'đ' | 'ð' => out.push('d'),
+            'ł' => out.push('l'),
+            'þ' => out.push_str("th"),
+            c if c.is_ascii_alphanumeric() => out.push(c),
+            // Latin letters lose their accents; kana dakuten and the like are kept
+            c if c.is_alphanumeric() => match std::iter::once(c).nfd().next() {
+                Some(base) if base.is_ascii() => out.extend(std::iter::once(c).nfd().filter(|m| !is_combining_mark(*m))),
+                _ => out.push(c),
+            },
+            _ => out.push(' '),
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// ---- candidate ranking ----
+
+/// Sort candidates best-first by how well they match the playing track.
+pub fn rank_candidates(track: &TrackQuery, candidates: Vec<Candidate>) -> Vec<(f32, Candidate)> {
+    let mut scored: Vec<(f32, Candidate)> = candidates
+        .into_iter()
+        .map(|c| (score_candidate(track, &c), c))
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    for (score, c) in &scored {
+        log::debug!("  candidate {:.2}: {} - {} ({}, {}s)", score, c.artist, c.title, c.album, c.duration);
+    }
+    scored
+}
+
+/// 0.0–1.0: title 40%, artist 30%, duration 30%, nudged towards entries that have lyrics
+/// and away from ones whose LRC tags name another track.
+fn score_candidate(track: &TrackQuery, c: &Candidate) -> f32 {
+    // as tagged, or with version notes and featured artists dropped on both sides
+    let title = similarity(&normalize(&track.title), &normalize(&c.title))
+        .max(similarity(&normalize(&clean_title(&track.title)), &normalize(&clean_title(&c.title))));
+    let artist = similarity(&normalize(&track.artist), &normalize(&c.artist))
+        .max(similarity(&normalize(&primary_artist(&track.artist)), &normalize(&primary_artist(&c.artist))));
+
+    // lrclib treats ±2s as the same recording; beyond 20s it's a different version
+    let duration = if track.duration == 0 || c.duration == 0 {
+        0.5
+    } else {
+        let delta = (track.duration as f32 - c.duration as f32).abs();
+        (1.0 - (delta - 2.0).max(0.0) / 18.0).max(0.0)
+    };
+
+    let lyrics = if !c.lyrics.synced.is_empty() {
+        1.0
+    } else if !c.lyrics.lines.is_empty() || c.lyrics.instrumental {
+        0.5
+    } else {
+        0.0
+    };
+
+    // the LRC's own ID tags disagreeing with the track is a strong signal
+    let tags = if metadata_mismatch(&c.lyrics.meta, track).is_some() { 0.5 } else { 1.0 };
+
+    (0.4 * title + 0.3 * artist + 0.3 * duration) * (0.8 + 0.2 * lyrics) * tags
+}
+
+/// Normalized Levenshtein similarity over chars.
+fn similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    1.0 - prev[b.len()] as f32 / longest as f32
+}
+
+impl LrcLibResult {
+    fn into_candidate(self) -> Candidate {
+        let (mut synced, meta) = match self.synced {
+            Some(ref s) => parse_lyrics(s, LyricsFormat::sniff(s)),
+            None => (vec![], LrcMetadata::default()),
+        };
+        let parallel = split_parallel(&mut synced);
+
+        let lines = if let Some(ref p) = self.plain {
+            p.lines().map(|s| s.to_string()).collect()
+        } else {
+            synced.iter().map(|l| l.text.clone()).collect()
+        };
+
+        Candidate {
+            artist: self.artist,
+            title: self.track,
+            album: self.album.unwrap_or_default(),
+            duration: self.duration.unwrap_or(0.0).round() as u32,
+            instrumental: self.instrumental,
+            lyrics: LyricsData {
+                lines,
+                synced,
+                source: Some(Provenance {
+                    provider: "lrclib".into(),
+                    source_id: Some(self.id.to_string()),
+                }),
+                meta,
+                instrumental: self.instrumental,
+                parallel,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ts_formats() {
+        let cases: &[(&str, Option<u32>)] = &[
+            ("00:00", Some(0)),
+            ("01:02", Some(62_000)),
+            ("01:02.5", Some(62_500)),
+            ("01:02.34", Some(62_340)),
+            ("01:02.345", Some(62_345)),
+            ("01:02:34", Some(62_340)),
+            ("1:02:03.45", Some(3_723_450)),
+            ("75:00.00", Some(4_500_000)),
+            (" 00:12.00 ", Some(12_000)),
+            ("00:60.00", None),
+            ("1:60:00.00", None),
+            ("00:12.3456", None),
+            ("ar:Artist", None),
+            ("-1:00.00", None),
+            ("", None),
+        ];
+        for (input, want) in cases {
+            assert_eq!(parse_ts(input), *want, "parse_ts({:?})", input);
+        }
+    }
+
+    #[test]
+    fn parse_lrc_lines() {
+        // (input, expected (time_ms, text) pairs)
+        let cases: &[(&str, &[(u32, &str)])] = &[
+            ("[00:01.00]One\n[00:02.00]Two", &[(1_000, "One"), (2_000, "Two")]),
+            ("[00:02.00]Two\n[00:01.00]One", &[(1_000, "One"), (2_000, "Two")]),
+            (
+                "[00:12.00][01:45.30]Chorus text",
+                &[(12_000, "Chorus text"), (105_300, "Chorus text")],
+            ),
+            ("[00:05:50]Colon centis", &[(5_500, "Colon centis")]),
+            ("[01:00:05.00]Hours", &[(3_605_000, "Hours")]),
+            ("[00:01.000]Millis", &[(1_000, "Millis")]),
+            ("[00:03]No fraction", &[(3_000, "No fraction")]),
+            ("[00:01.00]\n", &[(1_000, "")]),
+            ("plain text\n[bad]x", &[]),
+            ("[00:01.00][not a ts]Text", &[(1_000, "[not a ts]Text")]),
+        ];
+        for (input, want) in cases {
+            let (lines, _) = parse_lrc(input);
+            let got: Vec<(u32, &str)> = lines.iter().map(|l| (l.time_ms, l.text.as_str())).collect();
+            assert_eq!(got, *want, "parse_lrc({:?})", input);
+        }
+    }
+
+    #[test]
+    fn parse_lrc_repeated_line_moves_words() {
+        let (lines, _) = parse_lrc("[00:10.00][00:20.00]<00:10.00>la <00:10.50>la");
+        assert_eq!(lines.len(), 2);
+        let starts: Vec<Vec<u32>> = lines
+            .iter()
+            .map(|l| l.words.iter().map(|w| w.time_ms).collect())
+            .collect();
+        assert_eq!(starts, vec![vec![10_000, 10_500], vec![20_000, 20_500]]);
+    }
+
+    #[test]
+    fn karaoke_word_ends_and_spacing() {
+        let (lines, _) = parse_lrc(
+            "[00:01.00]<00:01.00>la <00:01.50>la  <00:02.00>la<00:02.40>\n[00:03.00]<00:03.00>end",
+        );
+        let words: Vec<(u32, u32, &str)> = lines[0]
+            .words
+            .iter()
+            .map(|w| (w.time_ms, w.end_ms, w.word.as_str()))
+            .collect();
+        assert_eq!(words, vec![(1_000, 1_500, "la "), (1_500, 2_000, "la  "), (2_000, 2_400, "la")]);
+        let joined: String = lines[0].words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(joined, lines[0].text);
+        // last word of the last line has nothing after it
+        assert_eq!(lines[1].words[0].end_ms, 3_000 + LAST_WORD_MS);
+    }
+
+    #[test]
+    fn karaoke_last_word_ends_at_next_line() {
+        let (lines, _) = parse_lrc("[00:01.00]<00:01.00>one <00:01.50>two\n[00:04.00]next");
+        assert_eq!(lines[0].words[1].end_ms, 4_000);
+    }
+
+    #[test]
+    fn parse_lrc_id_tags_and_offset() {
+        let (lines, meta) = parse_lrc(
+            "[ar:Artist]\n[ti:Title]\n[al:Album]\n[length: 03:25]\n[offset:+250]\n[00:01.00]Hi",
+        );
+        assert_eq!(meta.artist.as_deref(), Some("Artist"));
+        assert_eq!(meta.title.as_deref(), Some("Title"));
+        assert_eq!(meta.album.as_deref(), Some("Album"));
+        assert_eq!(meta.length_ms, Some(205_000));
+        assert_eq!(meta.offset_ms, 250);
+        assert_eq!(lines[0].time_ms, 750);
+    }
+
+    #[test]
+    fn metadata_mismatch_checks_id_tags() {
+        let t = TrackQuery { artist: "Artist".into(), title: "Song".into(), duration: 200, ..Default::default() };
+        let meta = |tags: &str| parse_lrc(tags).1;
+        assert!(metadata_mismatch(&meta("[ar:Artist]\n[ti:Song (Live)]\n[length:03:25]"), &t).is_none());
+        assert!(metadata_mismatch(&meta(""), &t).is_none());
+        assert!(metadata_mismatch(&meta("[ti:Another Tune]"), &t).unwrap().starts_with("[ti:"));
+        assert!(metadata_mismatch(&meta("[ar:Somebody Else]"), &t).unwrap().starts_with("[ar:"));
+        assert!(metadata_mismatch(&meta("[length:04:00]"), &t).unwrap().starts_with("[length:]"));
+        // no duration to compare against
+        let untimed = TrackQuery { duration: 0, ..t };
+        assert!(metadata_mismatch(&meta("[length:04:00]"), &untimed).is_none());
+    }
+
+    struct Fixed(LyricsData);
+
+    impl LyricsProvider for Fixed {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        fn fetch(&self, _track: &TrackQuery) -> Result<LyricsData, LyricsError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn provider_chain_follows_the_config() {
+        let cfg = Config { lyrics_providers: vec!["lrclib".into(), "bogus".into(), "local".into()], ..Config::default() };
+        let names: Vec<&str> = providers_from_config(&cfg).iter().map(|p| p.name()).collect();
+        assert_eq!(names, vec!["lrclib", "local"]);
+
+        // the first provider with lyrics wins
+        let (cache, _dir) = temp_cache("chain", 0);
+        let first = lyrics_from_text("[00:01.00]first", None);
+        let second = lyrics_from_text("[00:01.00]second", None);
+        let chain: Vec<Box<dyn LyricsProvider>> = vec![Box::new(Fixed(first)), Box::new(Fixed(second))];
+        assert_eq!(fetch_lyrics(&chain, &cache, &track("Song")).unwrap().lines, vec!["first"]);
+    }
+
+    #[test]
+    fn mismatched_lyrics_are_a_last_resort_and_not_cached() {
+        let (cache, _dir) = temp_cache("suspect", 0);
+        let wrong = lyrics_from_text("[ti:Another Tune]\n[00:01.00]wrong", None);
+        let right = lyrics_from_text("[ti:Song]\n[00:01.00]right", None);
+
+        let both: Vec<Box<dyn LyricsProvider>> = vec![Box::new(Fixed(wrong.clone())), Box::new(Fixed(right))];
+        let ld = fetch_lyrics(&both, &cache, &track("Song")).unwrap();
+        assert_eq!(ld.lines, vec!["right"]);
+        assert!(matches!(cache.lookup(&track("Song")), Lookup::Fresh(c) if c.lines == ["right"]));
+
+        let only_wrong: Vec<Box<dyn LyricsProvider>> = vec![Box::new(Fixed(wrong))];
+        let ld = fetch_lyrics(&only_wrong, &cache, &track("Other Song")).unwrap();
+        assert_eq!(ld.lines, vec!["wrong"]);
+        assert!(matches!(cache.lookup(&track("Other Song")), Lookup::Miss));
+    }
+
+    #[test]
+    fn karaoke_cjk_text_is_not_mangled() {
+        let (lines, _) = parse_lrc("[00:01.00]<00:01.00>夜に<00:01.80>駆ける<00:02.60>\n[00:05.00]사랑해 <00:05.00>");
+        assert_eq!(lines[0].text, "夜に駆ける");
+        let words: Vec<&str> = lines[0].words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, vec!["夜に", "駆ける"]);
+        assert_eq!(lines[0].words[1].end_ms, 2_600);
+        // untimed multibyte text before the first tag starts with the line
+        assert_eq!(lines[1].text, "사랑해 ");
+        assert_eq!(lines[1].words[0].time_ms, 5_000);
+    }
+
+    #[test]
+    fn karaoke_emoji_and_accents() {
+        let (lines, _) = parse_lrc("[00:02.00]<00:02.00>Café 🎶 <00:02.50>déjà vu 👋🏽<bad>!");
+        assert_eq!(lines[0].text, "Café 🎶 déjà vu 👋🏽<bad>!");
+        let words: Vec<&str> = lines[0].words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, vec!["Café 🎶 ", "déjà vu 👋🏽<bad>!"]);
+    }
+
+    #[test]
+    fn unclosed_tag_and_bom() {
+        let (lines, _) = parse_lrc("\u{feff}[00:01.00]ä <3 ö\n[00:02.00]<00:02.00>ü <éé");
+        assert_eq!(lines[0].text, "ä <3 ö");
+        assert!(lines[0].words.is_empty());
+        assert_eq!(lines[1].text, "ü <éé");
+        assert_eq!(lines[1].words[0].word, "ü <éé");
+    }
+
+    #[test]
+    fn angle_brackets_in_lyrics_are_text() {
+        let (lines, _) = parse_lrc("[00:01.00]<3 you >\n[00:02.00]<00:02.00>I <3 <00:02.50>you <00:03.00>");
+        assert_eq!(lines[0].text, "<3 you >");
+        assert!(lines[0].words.is_empty());
+        assert_eq!(lines[1].text, "I <3 you ");
+        let words: Vec<&str> = lines[1].words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, vec!["I <3 ", "you "]);
+    }
+
+    fn export_fixture() -> (LyricsData, TrackQuery) {
+        let (synced, meta) = parse_lrc("[00:01.00]<00:01.00>Hello <00:01.50>world<00:02.00>\n[00:03.00]\n[00:04.25]Bye");
+        let ld = LyricsData { synced, meta, ..Default::default() };
+        let track = TrackQuery { artist: "A".into(), title: "T".into(), duration: 10, ..Default::default() };
+        (ld, track)
+    }
+
+    #[test]
+    fn export_srt_cues_end_at_next_line() {
+        let (ld, track) = export_fixture();
+        let srt = render_export(&ld, &track, 250, LyricsFormat::Srt).unwrap();
+        assert_eq!(
+            srt,
+            "1\n00:00:00,750 --> 00:00:02,750\nHello world\n\n2\n00:00:04,000 --> 00:00:10,000\nBye\n\n"
+        );
+    }
+
+    #[test]
+    fn export_vtt_has_word_timestamps() {
+        let (ld, track) = export_fixture();
+        let vtt = render_export(&ld, &track, 0, LyricsFormat::Vtt).unwrap();
+        assert!(vtt.starts_with("WEBVTT\n\n00:00:01.000 --> 00:00:03.000\nHello <00:00:01.500>world\n"));
+        assert!(vtt.ends_with("00:00:04.250 --> 00:00:10.000\nBye\n\n"));
+    }
+
+    #[test]
+    fn export_lrc_round_trips() {
+        let (ld, track) = export_fixture();
+        let lrc = render_export(&ld, &track, 0, LyricsFormat::Lrc).unwrap();
+        assert!(lrc.starts_with("[ar:A]\n[ti:T]\n[length:00:10]\n"));
+        let (lines, meta) = parse_lrc(&lrc);
+        assert_eq!(meta.length_ms, Some(10_000));
+        assert_eq!(lines.len(), ld.synced.len());
+        for (a, b) in lines.iter().zip(&ld.synced) {
+            assert_eq!((a.time_ms, &a.text), (b.time_ms, &b.text));
+            let ta: Vec<(u32, u32)> = a.words.iter().map(|w| (w.time_ms, w.end_ms)).collect();
+            let tb: Vec<(u32, u32)> = b.words.iter().map(|w| (w.time_ms, w.end_ms)).collect();
+            assert_eq!(ta, tb);
+        }
+    }
+
+    #[test]
+    fn import_srt_with_gaps() {
+        let srt = "1\r\n00:00:01,000 --> 00:00:03,000\r\n<i>Hello</i>\r\nthere\r\n\r\n2\r\n00:00:03,000 --> 00:00:04,500\r\n{\\an8}Again\r\n\r\n3\r\n00:00:06,000 --> 00:00:07,000\r\nBye\r\n";
+        assert_eq!(LyricsFormat::sniff(srt), LyricsFormat::Srt);
+        let (lines, _) = parse_lyrics(srt, LyricsFormat::Srt);
+        let got: Vec<(u32, &str)> = lines.iter().map(|l| (l.time_ms, l.text.as_str())).collect();
+        assert_eq!(got, vec![(1_000, "Hello there"), (3_000, "Again"), (4_500, ""), (6_000, "Bye"), (7_000, "")]);
+        assert!(lines[0].words.is_empty());
+    }
+
+    #[test]
+    fn import_vtt_inline_timestamps() {
+        let vtt = "WEBVTT\n\nNOTE made by hand\n\nintro\n00:01.000 --> 00:02.000 align:center\n<v Singer>Hel<00:01.400>lo <00:01.600>you\n";
+        assert_eq!(LyricsFormat::sniff(vtt), LyricsFormat::Vtt);
+        let (lines, _) = parse_lyrics(vtt, LyricsFormat::Vtt);
+        assert_eq!(lines[0].text, "Hello you");
+        let words: Vec<(u32, u32, &str)> = lines[0].words.iter().map(|w| (w.time_ms, w.end_ms, w.word.as_str())).collect();
+        assert_eq!(words, vec![(1_000, 1_400, "Hel"), (1_400, 1_600, "lo "), (1_600, 2_000, "you")]);
+    }
+
+    #[test]
+    fn import_ttml_word_spans() {
+        let ttml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<tt xmlns="http://www.w3.org/ns/ttml" xmlns:ttm="http://www.w3.org/ns/ttml#metadata"><body dur="30.000"><div>
+<p begin="00:00:01.000" end="00:00:02.500"><span begin="1.000s" end="1.500s">夜に</span> <span begin="1.5s" end="2.4s">駆ける</span><span ttm:role="x-bg"><span begin="2.0" end="2.4">(ah)</span></span></p>
+<p begin="0:03.5" end="0:05">Plain line</p>
+</div></body></tt>"#;
+        assert_eq!(LyricsFormat::sniff(ttml), LyricsFormat::Ttml);
+        let (lines, _) = parse_lyrics(ttml, LyricsFormat::Ttml);
+        let got: Vec<(u32, &str)> = lines.iter().map(|l| (l.time_ms, l.text.as_str())).collect();
+        assert_eq!(got, vec![(1_000, "夜に 駆ける"), (2_500, ""), (3_500, "Plain line"), (5_000, "")]);
+        let words: Vec<(u32, u32, &str)> = lines[0].words.iter().map(|w| (w.time_ms, w.end_ms, w.word.as_str())).collect();
+        assert_eq!(words, vec![(1_000, 1_500, "夜に "), (1_500, 2_400, "駆ける")]);
+        assert!(lines[2].words.is_empty());
+    }
+
+    #[test]
+    fn sniff_defaults_to_lrc() {
+        assert_eq!(LyricsFormat::sniff("[00:01.00]Hi"), LyricsFormat::Lrc);
+        assert_eq!(LyricsFormat::sniff("just words"), LyricsFormat::Lrc);
+    }
+
+    /// Removes its directory when dropped, so a failed assert doesn't leak it.
+    pub(crate) struct TempDir(pub std::path::PathBuf);
+
+    impl TempDir {
+        pub(crate) fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("sonix_lyrics_test_{}_{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    pub(crate) fn temp_cache(name: &str, max_entries: usize) -> (LyricsCache, TempDir) {
+        let dir = TempDir::new(name);
+        let cache = LyricsCache {
+            dir: dir.0.clone(),
+            ttl: Some(chrono::Duration::days(1)),
+            negative_ttl: chrono::Duration::hours(1),
+            max_entries,
+            romanize: false,
+        };
+        (cache, dir)
+    }
+
+    pub(crate) fn track(title: &str) -> TrackQuery {
+        TrackQuery { artist: "Artist".into(), title: title.into(), ..Default::default() }
+    }
+
+    #[test]
+    fn cache_negative_entries_and_lru_eviction() {
+        let (cache, _dir) = temp_cache("lru", 2);
+        let ld = LyricsData { lines: vec!["la".into()], ..Default::default() };
+
+        cache.store_not_found(&track("missing"));
+        assert!(matches!(cache.lookup(&track("missing")), Lookup::NotFound));
+
+        cache.store(&track("pinned"), &LyricsData { pinned: true, ..ld.clone() });
+        cache.store(&track("old"), &ld);
+        cache.store(&track("new"), &ld);
+        // "missing" and "old" go; the pinned entry stays whatever its age
+        assert!(matches!(cache.lookup(&track("missing")), Lookup::Miss));
+        assert!(matches!(cache.lookup(&track("old")), Lookup::Miss));
+        assert!(matches!(cache.lookup(&track("pinned")), Lookup::Fresh(_)));
+        assert!(matches!(cache.lookup(&track("new")), Lookup::Fresh(_)));
+
+        // an expired entry is still served as stale
+        let mut index = cache.load_index();
+        index.entries.values_mut().for_each(|e| e.fetched_at -= chrono::Duration::days(2));
+        cache.save_index(&index);
+        assert!(matches!(cache.lookup(&track("new")), Lookup::Stale(_)));
+        assert!(matches!(cache.lookup(&track("pinned")), Lookup::Fresh(_)));
+    }
+
+    #[test]
+    fn offsets_go_with_their_cache_entry() {
+        let (cache, _dir) = temp_cache("offsets", 2);
+        let ld = LyricsData { lines: vec!["la".into()], ..Default::default() };
+        let offset = |t: &str| cache.dir.join(format!("{}.offset", cache_key(&track(t))));
+        for t in ["a", "b"] {
+            cache.store(&track(t), &ld);
+            std::fs::write(offset(t), "250").unwrap();
+        }
+        // LRU eviction
+        cache.store(&track("c"), &ld);
+        assert!(!offset("a").exists() && offset("b").exists());
+
+        let bytes = cache.stats().bytes;
+        assert!(cache.remove(&cache_key(&track("b"))));
+        assert!(!offset("b").exists());
+        assert!(cache.stats().bytes < bytes);
+
+        std::fs::write(offset("c"), "-100").unwrap();
+        cache.purge(true);
+        assert!(!offset("c").exists());
+    }
+
+    #[test]
+    fn not_found_replaces_the_stored_lyrics() {
+        let (cache, _dir) = temp_cache("not-found", 0);
+        let t = track("gone");
+        let path = cache.entry_path(&cache_key(&t));
+        cache.store(&t, &LyricsData { lines: vec!["la".into()], ..Default::default() });
+        assert!(path.exists());
+
+        cache.store_not_found(&t);
+        assert!(!path.exists());
+        let st = cache.stats();
+        assert_eq!((st.entries, st.not_found, st.bytes), (1, 1, 0));
+    }
+
+    #[test]
+    fn cache_keys_do_not_collide() {
+        let a = TrackQuery { artist: "AC/DC".into(), title: "T.N.T.".into(), ..Default::default() };
+        let b = TrackQuery { artist: "AC DC".into(), title: "T N T ".into(), ..Default::default() };
+        assert_eq!(legacy_key(&a.artist, &a.title), legacy_key(&b.artist, &b.title));
+        assert_ne!(cache_key(&a), cache_key(&b));
+        // same title, different versions of the song
+        let live = TrackQuery { id: "abc123".into(), ..a.clone() };
+        let studio = TrackQuery { id: "def456".into(), ..a.clone() };
+        assert_ne!(cache_key(&live), cache_key(&studio));
+        assert!(cache_key(&live).starts_with("id-abc123-"));
+        // the file was retagged as another song
+        let retagged = TrackQuery { title: "Thunderstruck".into(), ..live.clone() };
+        assert_ne!(cache_key(&live), cache_key(&retagged));
+    }
+
+    #[test]
+    fn cache_migrates_legacy_files_and_finds_other_ids() {
+        let (cache, _dir) = temp_cache("migrate", 0);
+        std::fs::create_dir_all(&cache.dir).unwrap();
+        let legacy = cache.dir.join("artist_song.json");
+        std::fs::write(&legacy, r#"{"lines":["old"],"synced":[],"pinned":true}"#).unwrap();
+
+        let first = TrackQuery { id: "one".into(), artist: "Artist".into(), title: "Song".into(), duration: 200, ..Default::default() };
+        let Lookup::Fresh(ld) = cache.lookup(&first) else { panic!("legacy file not adopted") };
+        assert!(ld.pinned && ld.lines == ["old"]);
+        assert!(!legacy.exists());
+        let on_disk = std::fs::read_to_string(cache.entry_path(&cache_key(&first))).unwrap();
+        assert!(on_disk.contains(&format!("\"version\":{}", CACHE_VERSION)));
+
+        // re-scanned library: new id, same track
+        let rescanned = TrackQuery { id: "two".into(), duration: 201, ..first.clone() };
+        assert!(matches!(cache.lookup(&rescanned), Lookup::Fresh(_)));
+        let other = TrackQuery { id: "three".into(), duration: 320, ..first };
+        assert!(matches!(cache.lookup(&other), Lookup::Miss));
+    }
+
+    #[test]
+    fn cache_pin_purge_and_stats() {
+        let (cache, _dir) = temp_cache("manage", 0);
+        let synced = LyricsData {
+            synced: vec![SyncedLine { time_ms: 0, text: "la".into(), words: vec![] }],
+            ..Default::default()
+        };
+        cache.store(&track("keep"), &synced);
+        cache.store(&track("drop"), &LyricsData::default());
+        cache.store_not_found(&track("missing"));
+
+        let key = cache_key(&track("keep"));
+        assert!(cache.set_pinned(&key, true));
+        assert!(cache.entry(&key).unwrap().1.unwrap().pinned);
+
+        let st = cache.stats();
+        assert_eq!((st.entries, st.synced, st.plain, st.not_found, st.pinned), (3, 1, 1, 1, 1));
+        assert!(cache.entries().iter().all(|(_, e)| e.refetchable()));
+
+        // a file the index doesn't know, without [ti:] to say what it was
+        std::fs::write(cache.entry_path("stray"), r#"{"version":2,"lines":["?"],"synced":[]}"#).unwrap();
+        let (_, stray) = cache.entries().into_iter().find(|(k, _)| k == "stray").unwrap();
+        assert!(!stray.refetchable());
+        assert!(cache.remove("stray"));
+        assert_eq!(cache.purge(false), 1);
+        assert_eq!(cache.purge(true), 1);
+        assert_eq!(cache.entries().len(), 1);
+        assert!(cache.remove(&key));
+        assert!(cache.entries().is_empty());
+    }
+
+    #[test]
+    fn instrumental_is_carried_and_cached() {
+        let hit: LrcLibResult = serde_json::from_str(
+            r#"{"id":7,"trackName":"Intro","artistName":"Artist","duration":90,"instrumental":true,"plainLyrics":null,"syncedLyrics":null}"#,
+        )
+        .unwrap();
+        let c = hit.into_candidate();
+        assert!(c.lyrics.instrumental && c.lyrics.lines.is_empty());
+
+        let (cache, _dir) = temp_cache("instrumental", 0);
+        cache.store(&track("Intro"), &c.lyrics);
+        assert!(matches!(cache.lookup(&track("Intro")), Lookup::Fresh(ld) if ld.instrumental));
+        assert_eq!(cache.entries()[0].1.kind(), "instrumental");
+        assert_eq!(cache.stats().instrumental, 1);
+
+        assert!(instrumental_title("Song (Instrumental)"));
+        assert!(instrumental_title("Song - Off Vocal"));
+        assert!(!instrumental_title("Instrumental"));
+        assert!(!instrumental_title("Song"));
+    }
+
+    #[test]
+    fn instrumental_tags_skip_lookups_but_titles_are_a_fallback() {
+        let (cache, _dir) = temp_cache("instrumental_title", 0);
+        let lyrics: Vec<Box<dyn LyricsProvider>> = vec![Box::new(Fixed(lyrics_from_text("[00:01.00]la la", None)))];
+
+        // genre/mood tags: no provider is asked
+        let tagged = TrackQuery { instrumental: true, ..track("Song") };
+        let ld = fetch_lyrics(&lyrics, &cache, &tagged).unwrap();
+        assert!(ld.instrumental && ld.lines.is_empty());
+
+        // a title alone doesn't stop a provider that has lyrics
+        let ld = fetch_lyrics(&lyrics, &cache, &track("Song (Instrumental)")).unwrap();
+        assert_eq!(ld.lines, vec!["la la"]);
+
+        // nobody has lyrics: the title decides, but only the miss is cached
+        let t = track("Theme - Off Vocal");
+        for _ in 0..2 {
+            let ld = fetch_lyrics(&[], &cache, &t).unwrap();
+            assert!(ld.instrumental);
+            assert!(matches!(cache.lookup(&t), Lookup::NotFound));
+        }
+        assert!(matches!(fetch_lyrics(&[], &cache, &track("Theme")), Err(LyricsError::NotFound)));
+    }
+
+    #[test]
+    fn parallel_lines_from_shared_timestamps_and_second_file() {
+        let ld = lyrics_from_text(
+            "[00:01.00]<00:01.00>夜に<00:01.80>駆ける<00:02.60>\n[00:01.00]Racing into the night\n[00:03.00]沈むように\n[00:05.00]溶けてゆくように\n[00:05.00]Like melting away\n[00:05.00]toketeyuku you ni",
+            None,
+        );
+        assert_eq!(ld.lines, vec!["夜に駆ける", "沈むように", "溶けてゆくように"]);
+        assert_eq!(ld.parallel.len(), 2);
+        assert_eq!(ld.parallel[0].lines, vec!["Racing into the night", "", "Like melting away"]);
+        assert_eq!(ld.parallel[1].lines, vec!["", "", "toketeyuku you ni"]);
+        // the translation at the same time doesn't cut the last word short
+        assert_eq!(ld.synced[0].words[1].end_ms, 2600);
+
+        let other = lyrics_from_text("[00:01.20]yoru ni kakeru\n[00:03.90]shizumu you ni\n[00:09.00]stray", None);
+        let romaji = align_parallel(&ld.synced, &other.synced, "romaji").unwrap();
+        assert_eq!(romaji.label, "romaji");
+        assert_eq!(romaji.lines, vec!["yoru ni kakeru", "", ""]);
+        assert!(align_parallel(&ld.synced, &[], "en").is_none());
+    }
+
+    #[test]
+    fn navidrome_structured_lyrics_to_lyrics_data() {
+        let mut all = navidrome::tests::lyrics_reply(navidrome::tests::LYRICS_REPLY).unwrap();
+        let plain = structured_to_lyrics(all.pop().unwrap(), "s1");
+        assert_eq!(plain.lines, vec!["Hello world", "Bye"]);
+        assert!(plain.synced.is_empty());
+
+        let ld = structured_to_lyrics(all.pop().unwrap(), "s1");
+        assert_eq!(ld.source, Some(Provenance { provider: "navidrome".into(), source_id: Some("s1".into()) }));
+        assert_eq!(ld.lines, vec!["Hello world", "Bye"]);
+        // a negative offset shows the lines later
+        let times: Vec<(u32, &str)> = ld.synced.iter().map(|l| (l.time_ms, l.text.as_str())).collect();
+        assert_eq!(times, vec![(1250, "Hello world"), (3250, "Bye")]);
+        let words: Vec<(u32, u32)> = ld.synced[0].words.iter().map(|w| (w.time_ms, w.end_ms)).collect();
+        assert_eq!(words, vec![(1250, 1750), (1750, 3250)]);
+        assert_eq!(ld.parallel.len(), 1);
+        assert_eq!(ld.parallel[0].lines, vec!["Hallo Welt", ""]);
+    }
+
+    #[test]
+    fn navidrome_translations_skip_copies_of_the_original() {
+        let entry = |lang: &str, texts: &[&str]| StructuredLyrics {
+            lang: lang.into(),
+            synced: true,
+            offset_ms: 0,
+            lines: texts
+                .iter()
+                .enumerate()
+                .map(|(i, t)| navidrome::StructuredLine { start_ms: Some(i as u32 * 1000), value: t.to_string() })
+                .collect(),
+        };
+        let main = structured_to_lyrics(entry("jpn", &["夜に", "駆ける"]), "s1");
+        let others = vec![
+            entry("eng", &["At night", "Racing"]),
+            entry("jpn", &["夜に", "かける"]),
+            entry("xxx", &["夜に", "駆ける"]),
+            StructuredLyrics { synced: false, ..entry("deu", &["Nachts", "Rennen"]) },
+            entry("", &["yoru ni", "kakeru"]),
+        ];
+        let rows = other_languages(&main.synced, "jpn", others, "s1");
+        let got: Vec<(&str, Vec<&str>)> =
+            rows.iter().map(|p| (p.label.as_str(), p.lines.iter().map(String::as_str).collect())).collect();
+        assert_eq!(got, vec![("eng", vec!["At night", "Racing"]), ("translation", vec!["yoru ni", "kakeru"])]);
+    }
+
+    #[test]
+    fn local_lyrics_lookup_prefers_lrc() {
+        let dir = TempDir::new("local");
+        let write = |rel: &str, text: &str| {
+            let p = dir.0.join(rel);
+            std::fs::create_dir_all(p.parent().unwrap()).unwrap();
+            std::fs::write(p, text).unwrap();
+        };
+        write("Artist - Song.txt", "plain words");
+        write("artist - song!.lrc", "[00:01.00]synced words");
+        write("Other - Song.lrc", "[00:01.00]someone else");
+        write("song-id-7.txt", "by id");
+        write("Artist/Album/Song.txt", "templated plain");
+        write("Artist/Album/Song.lrc", "[00:02.00]templated synced");
+
+        let t = TrackQuery { album: "Album".into(), ..track("Song") };
+        let flat = LocalLyrics { dir: dir.0.clone(), template: String::new() };
+        let ld = flat.fetch(&t).unwrap();
+        assert_eq!(ld.lines, vec!["synced words"]);
+        assert!(ld.source.unwrap().source_id.unwrap().ends_with("artist - song!.lrc"));
+        assert_eq!(flat.fetch(&TrackQuery { id: "song-id-7".into(), ..track("Unknown") }).unwrap().lines, vec!["by id"]);
+        assert!(matches!(flat.fetch(&track("Missing")), Err(LyricsError::NotFound)));
+        assert_eq!(flat.save_path(&t), dir.0.join("Artist - Song.lrc"));
+
+        let templated = LocalLyrics { dir: dir.0.clone(), template: "{artist}/{album}/{title}".into() };
+        assert_eq!(templated.fetch(&t).unwrap().lines, vec!["templated synced"]);
+        assert_eq!(templated.save_path(&t), dir.0.join("Artist/Album/Song.lrc"));
+        // nothing at the template path: the flat names still count
+        let elsewhere = TrackQuery { album: "Single".into(), ..t };
+        assert_eq!(templated.fetch(&elsewhere).unwrap().lines, vec!["synced words"]);
+    }
+
+    #[test]
+    fn timed_lyrics_are_saved_locally_and_pinned() {
+        let (cache, _dir) = temp_cache("timed", 0);
+        let dir = cache.dir.join("lyrics");
+        let cfg = Config { local_lyrics_dir: dir.display().to_string(), ..Config::default() };
+        let t = TrackQuery { duration: 10, ..track("Tapped") };
+        let word = |time_ms, w: &str| KaraokeWord { time_ms, end_ms: 0, word: w.into() };
+        let synced = vec![
+            SyncedLine { time_ms: 4000, text: "second".into(), words: vec![] },
+            SyncedLine { time_ms: 1000, text: "one two".into(), words: vec![word(1000, "one "), word(1500, "two")] },
+        ];
+
+        let (path, ld) = save_timed_lyrics(&cfg, &cache, &t, synced).unwrap();
+        assert_eq!(path, dir.join("Artist - Tapped.lrc"));
+        assert!(ld.pinned);
+        assert_eq!(ld.lines, vec!["one two", "second"]);
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.contains("[00:01.00]<00:01.00>one <00:01.50>two\n[00:04.00]second"), "{}", text);
+
+        let local = LocalLyrics::new(&cfg).fetch(&t).unwrap();
+        assert_eq!(local.synced[0].words[1].end_ms, 4000);
+        assert!(matches!(cache.lookup(&t), Lookup::Fresh(c) if c.pinned));
+    }
+
+    #[test]
+    fn clean_title_drops_version_notes_only() {
+        assert_eq!(clean_title("Heroes (2017 Remaster)"), "Heroes");
+        assert_eq!(clean_title("Song - Live at Wembley [Deluxe Edition]"), "Song");
+        assert_eq!(clean_title("Song – 2011 Remastered Version"), "Song");
+        assert_eq!(clean_title("Song (feat. Someone)"), "Song");
+        assert_eq!(clean_title("Song ft. Someone"), "Song");
+        assert_eq!(clean_title("Song (Instrumental)"), "Song (Instrumental)");
+        assert_eq!(clean_title("Love Me Do (Part 2)"), "Love Me Do (Part 2)");
+        assert_eq!(clean_title("(Live)"), "(Live)");
+    }
+
+    #[test]
+    fn split_featured_artists() {
+        let (primary, feat) = split_artists("Artist feat. Other & Third");
+        assert_eq!(primary, "Artist");
+        assert_eq!(feat, vec!["Other", "Third"]);
+        assert_eq!(split_artists("Simon & Garfunkel").0, "Simon & Garfunkel");
+        assert_eq!(split_artists("A; B").1, vec!["B"]);
+        assert_eq!(split_artists("A / B • C").1, vec!["B", "C"]);
+        assert_eq!(split_artists("AC/DC"), ("AC/DC".to_string(), vec![]));
+        assert_eq!(split_artists("AC/DC feat. Guest").0, "AC/DC");
+        assert_eq!(split_artists("A FT B, C").1, vec!["B", "C"]);
+    }
+
+    #[test]
+    fn normalize_folds_diacritics_and_punctuation() {
+        assert_eq!(normalize("Beyoncé"), "beyonce");
+        assert_eq!(normalize("Don't Stop Me Now!"), "dont stop me now");
+        assert_eq!(normalize("Sigur Rós & Björk"), "sigur ros and bjork");
+        assert_eq!(normalize("Mø — Strauß"), "mo strauss");
+        assert_eq!(normalize("ハルジオン"), "ハルジオン");
+        assert_eq!(normalize("Привет, мир"), "привет мир");
+    }
+
+    #[test]
+    fn query_ladder_gets_looser() {
+        let t = TrackQuery {
+            artist: "Artist feat. Guest".into(),
+            title: "Café (2011 Remaster)".into(),
+            album: "Album".into(),
+            duration: 200,
+            ..Default::default()
+        };
+        let steps: Vec<String> = query_ladder(&t).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            steps,
+            vec![
+                "get \"Artist feat. Guest\" / \"Café (2011 Remaster)\" / \"Album\" / 200s",
+                "get \"Artist\" / \"Café\" / \"Album\" / 200s",
+                "search \"Artist feat. Guest Café (2011 Remaster)\"",
+                "search \"Artist Café\"",
+                "search \"artist cafe\"",
+                "search \"cafe\"",
+            ]
+        );
+
+        let plain = TrackQuery { artist: "A".into(), title: "B".into(), ..Default::default() };
+        let steps: Vec<String> = query_ladder(&plain).iter().map(ToString::to_string).collect();
+        assert_eq!(steps, vec!["search \"A B\"", "search \"a b\"", "search \"b\""]);
+    }
+
+    #[test]
+    fn ranking_prefers_the_track_and_rejects_decoys() {
+        let t = TrackQuery { artist: "Queen".into(), title: "Bohemian Rhapsody".into(), duration: 355, ..Default::default() };
+        let synced = LyricsData {
+            synced: vec![SyncedLine { time_ms: 0, text: "Is this the real life?".into(), words: vec![] }],
+            ..Default::default()
+        };
+        let candidate = |artist: &str, title: &str, duration| Candidate {
+            artist: artist.into(),
+            title: title.into(),
+            album: String::new(),
+            duration,
+            instrumental: false,
+            lyrics: synced.clone(),
+        };
+        let ranked = rank_candidates(
+            &t,
+            vec![
+                candidate("Queen Tribute Band", "Bohemian Rhapsody (Karaoke)", 240),
+                candidate("Some Band", "Rhapsody in Blue", 355),
+                candidate("Queen", "Bohemian Rhapsody - Remastered 2011", 356),
+            ],
+        );
+        assert_eq!(ranked[0].1.artist, "Queen");
+        assert!(ranked[0].0 >= GOOD_MATCH);
+        // sharing a word of the title or just the length doesn't make it the song
+        for (score, c) in &ranked[1..] {
+            assert!(*score < MIN_MATCH, "{} scored {:.2}", c.artist, score);
+        }
+    }
+
+    #[test]
+    fn romanized_lines_become_a_parallel_row() {
+        let ld = lyrics_from_text("[00:01.00]사랑해\n[00:02.00]la la", None);
+        let ld = romanized(ld, true);
+        assert_eq!(ld.parallel.len(), 1);
+        assert_eq!(ld.parallel[0].lines, vec!["saranghae", ""]);
+        assert_eq!(romanized(ld.clone(), true).parallel.len(), 1);
+        assert!(romanized(ld, false).parallel.is_empty());
+    }
 }
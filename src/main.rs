@@ -1,7 +1,11 @@
+mod cli;
 mod config;
 mod lyrics;
+mod mpv;
 mod navidrome;
+mod romanize;
 mod setup;
+mod worker;
 
 use chrono::{DateTime, Utc};
 use crossbeam_channel::{bounded, select};
@@ -21,14 +25,16 @@ use ratatui::{
     Frame, Terminal,
 };
 use simplelog::*;
-use std::fs::File;
+use std::fs::{File, OpenOptions};
+use serde_json::json;
 use std::io::{self, Write};
-use std::os::unix::net::UnixStream;
 use std::time::{Duration, Instant};
 
 use config::Config;
-use lyrics::SyncedLine;
-use navidrome::{get_playlist_tracks, get_playlists, Playlist, PlaylistTrack};
+use lyrics::{CacheEntry, Candidate, KaraokeWord, LyricsCache, LyricsData, LyricsError, ParallelLines, SyncedLine, TrackQuery};
+use mpv::MpvClient;
+use worker::{Loaded, Reply, Request, Worker};
+use navidrome::{get_playlist_tracks, get_playlists, NavidromeError, NowPlaying, Playlist, PlaylistTrack};
 
 const MPV_SOCK: &str = "/tmp/sonix_lyrics_mpv.sock";
 
@@ -65,6 +71,33 @@ enum AppView {
     Queue,
     Playlists,
     Settings,
+    LyricsSearch,
+    Cache,
+    SyncEditor,
+}
+
+/// A line in the timing editor (F7), timed as the lyrics clock sees it.
+struct SyncLine {
+    text: String,
+    time_ms: Option<u32>,
+    /// Words with the whitespace after them, timed in word mode.
+    words: Vec<(String, Option<u32>)>,
+}
+
+impl SyncLine {
+    fn new(text: &str) -> Self {
+        let words = text.split_inclusive(char::is_whitespace).map(|w| (w.to_string(), None)).collect();
+        SyncLine { text: text.to_string(), time_ms: None, words }
+    }
+
+    /// Move the line and any word times with it.
+    fn shift(&mut self, delta_ms: i64) {
+        let shift = |t: u32| (t as i64 + delta_ms).max(0) as u32;
+        self.time_ms = self.time_ms.map(shift);
+        for (_, t) in &mut self.words {
+            *t = t.map(shift);
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -79,6 +112,7 @@ enum PlaylistFocus {
 struct AppState {
     config: Config,
 
+    track_id: String,
     title: String,
     artist: String,
     album: String,
@@ -89,10 +123,18 @@ struct AppState {
 
     raw_lyrics: Vec<String>,
     synced: Vec<SyncedLine>,
+    lyrics_source: Option<String>,
+    instrumental: bool,
+    parallel: Vec<ParallelLines>,
+    lyrics_cache: LyricsCache,
+    worker: Worker,
     cached_lines: Vec<Line<'static>>,
     current_line: u16,
     scroll: u16,
-    current_word: Option<String>,
+    lyric_cursor: Option<usize>, // selected synced line for jump-to-line
+    lyrics_offset_ms: i64,       // per-track nudge, positive = lyrics sooner
+    current_word: Option<usize>, // index into the current line's karaoke words
+    lyrics_ms: u32,              // playback position + offset, as the lyrics see it
 
     cover_art_lines: Vec<Line<'static>>,  // halfblock fallback
     cover_art_kitty: Option<(Vec<u8>, u32, u32)>, // (rgba_bytes, img_w, img_h) for Kitty
@@ -113,11 +155,33 @@ struct AppState {
     // Queue (F2)
     queue_cursor: usize,
 
+    // Lyrics search (F5)
+    picker_results: Vec<(f32, Candidate)>,
+    picker_track_id: String,
+    picker_cursor: usize,
+    picker_query: String,
+    picker_editing: bool,
+
+    // Lyrics cache (F6)
+    cache_entries: Vec<(String, CacheEntry)>,
+    cache_cursor: usize,
+
+    // Timing editor (F7)
+    sync_track: TrackQuery, // the track the lines belong to, saved against
+    sync_lines: Vec<SyncLine>,
+    sync_cursor: usize,
+    sync_word: usize, // next word to tap in word mode
+    sync_word_mode: bool,
+
     // Playback
     jukebox_playing: bool,
     jukebox_gain: f32,
     jukebox_index: i32,
     mpv_process: Option<std::process::Child>,
+    player: Option<MpvClient>,
+
+    // Follow mode — last getNowPlaying poll
+    follow_last_poll: Option<Instant>,
 
     // Settings (F4) — transient editable values
     settings_cursor: usize,
@@ -127,6 +191,7 @@ struct AppState {
     settings_user: String,
     settings_refresh: String,
     settings_karaoke: bool,
+    settings_follow: bool,
 
     show_help: bool,
 }
@@ -137,10 +202,14 @@ impl AppState {
         let settings_user = config.navidrome_user.clone();
         let settings_refresh = config.refresh_interval.to_string();
         let settings_karaoke = config.karaoke_enabled;
+        let settings_follow = config.follow_mode;
+        let lyrics_cache = LyricsCache::new(&config);
+        let worker = Worker::spawn(&config);
 
         Self {
             config,
 
+            track_id: String::new(),
             title: String::new(),
             artist: String::new(),
             album: String::new(),
@@ -151,10 +220,18 @@ impl AppState {
 
             raw_lyrics: vec!["Press F3 or 'p' to open your playlists.".into()],
             synced: vec![],
+            lyrics_source: None,
+            instrumental: false,
+            parallel: vec![],
+            lyrics_cache,
+            worker,
             cached_lines: vec![Line::from("Press F3 or 'p' to open your playlists.")],
             current_line: 0,
             scroll: 0,
+            lyric_cursor: None,
+            lyrics_offset_ms: 0,
             current_word: None,
+            lyrics_ms: 0,
 
             cover_art_lines: vec![],
             cover_art_kitty: None,
@@ -174,10 +251,28 @@ impl AppState {
 
             queue_cursor: 0,
 
+            picker_results: vec![],
+            picker_track_id: String::new(),
+            picker_cursor: 0,
+            picker_query: String::new(),
+            picker_editing: false,
+
+            cache_entries: vec![],
+            cache_cursor: 0,
+
+            sync_track: TrackQuery::default(),
+            sync_lines: vec![],
+            sync_cursor: 0,
+            sync_word: 0,
+            sync_word_mode: false,
+
             jukebox_playing: false,
             jukebox_gain: 0.7,
             jukebox_index: 0,
             mpv_process: None,
+            player: None,
+
+            follow_last_poll: None,
 
             settings_cursor: 0,
             settings_editing: false,
@@ -186,6 +281,7 @@ impl AppState {
             settings_user,
             settings_refresh,
             settings_karaoke,
+            settings_follow,
 
             show_help: false,
         }
@@ -196,6 +292,7 @@ impl AppState {
         self.settings_user = self.config.navidrome_user.clone();
         self.settings_refresh = self.config.refresh_interval.to_string();
         self.settings_karaoke = self.config.karaoke_enabled;
+        self.settings_follow = self.config.follow_mode;
     }
 
     fn save_settings(&mut self) {
@@ -203,10 +300,33 @@ impl AppState {
         self.config.navidrome_user = self.settings_user.trim().to_string();
         self.config.refresh_interval = self.settings_refresh.trim().parse().unwrap_or(2);
         self.config.karaoke_enabled = self.settings_karaoke;
+        self.set_follow_mode(self.settings_follow);
         self.config.save();
+        self.lyrics_cache = LyricsCache::new(&self.config);
+        self.worker.send(Request::Reconfigure(self.config.clone()));
         self.status = "Settings saved.".into();
         info!("Settings saved");
     }
+
+    fn set_follow_mode(&mut self, on: bool) {
+        if on && !self.config.follow_mode {
+            // sonix becomes a lyrics companion; another client owns playback
+            kill_mpv(self);
+            self.jukebox_playing = false;
+            self.track_id.clear();
+            self.follow_last_poll = None;
+        }
+        self.config.follow_mode = on;
+        self.settings_follow = on;
+    }
+
+    fn mpv_command(&mut self, args: serde_json::Value) {
+        if let Some(ref mut player) = self.player {
+            if let Err(e) = player.command(args) {
+                error!("mpv command failed: {}", e);
+            }
+        }
+    }
 }
 
 // ----------------------------------------
@@ -225,21 +345,16 @@ fn spawn_mpv(url: &str) -> Option<std::process::Child> {
         .ok()
 }
 
-fn kill_mpv(process: &mut Option<std::process::Child>) {
-    if let Some(ref mut child) = process {
+fn kill_mpv(app: &mut AppState) {
+    app.player = None;
+    if let Some(ref mut child) = app.mpv_process {
         let _ = child.kill();
         let _ = child.wait();
     }
-    *process = None;
+    app.mpv_process = None;
     let _ = std::fs::remove_file(MPV_SOCK);
 }
 
-fn mpv_ipc(cmd: &str) {
-    if let Ok(mut sock) = UnixStream::connect(MPV_SOCK) {
-        let _ = sock.write_all(cmd.as_bytes());
-    }
-}
-
 // ----------------------------------------
 // Cover art
 // ----------------------------------------
@@ -268,7 +383,7 @@ fn render_cover_art_halfblock(img: &image::DynamicImage, cols: u32, rows: u32) -
 
 fn b64_encode(data: &[u8]) -> String {
     const A: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
     for c in data.chunks(3) {
         let n = match c.len() {
             3 => (c[0] as u32) << 16 | (c[1] as u32) << 8 | c[2] as u32,
@@ -319,7 +434,11 @@ fn play_track(app: &mut AppState, idx: usize) {
     if idx >= app.tracks.len() {
         return;
     }
-    kill_mpv(&mut app.mpv_process);
+    kill_mpv(app);
+    if app.config.follow_mode {
+        app.set_follow_mode(false);
+        info!("Follow mode disabled by local playback");
+    }
 
     let pt = app.tracks[idx].clone();
     let url = navidrome::stream_url(&app.config, &pt.id);
@@ -329,23 +448,41 @@ fn play_track(app: &mut AppState, idx: usize) {
     app.jukebox_index = idx as i32;
     app.queue_cursor = idx;
 
+    load_track(app, &pt, Utc::now());
+    app.status = format!("Now playing: {} — {}", pt.artist, pt.title);
+
+    let upcoming = app.tracks.iter().skip(idx + 1).take(app.config.prefetch_tracks).cloned().collect();
+    app.worker.send(Request::Prefetch(upcoming));
+}
+
+/// Metadata for a track, independent of who is playing it. Cover art and lyrics
+/// are fetched by the worker and filled in by `apply_loaded`.
+fn load_track(app: &mut AppState, pt: &PlaylistTrack, started: DateTime<Utc>) {
+    app.track_id = pt.id.clone();
     app.title = pt.title.clone();
     app.artist = pt.artist.clone();
-    app.album = String::new();
+    app.album = pt.album.clone();
     app.duration_seconds = pt.duration;
-    app.start_timestamp_utc = Some(Utc::now());
+    app.start_timestamp_utc = Some(started);
     app.progress_seconds = 0;
     app.progress = 0.0;
-    app.current_line = 0;
-    app.scroll = 0;
 
-    // fetch cover art
-    let art_img = pt.cover_art_id.as_deref()
-        .and_then(|id| navidrome::fetch_cover_art_bytes(&app.config, id))
-        .and_then(|bytes| image::load_from_memory(&bytes).ok());
+    app.cover_art_kitty = None;
+    app.cover_art_lines = vec![];
+    apply_lyrics(app, LyricsData { lines: vec!["Loading lyrics…".into()], ..Default::default() });
+    app.lyrics_offset_ms = lyrics::load_offset(&current_track_query(app));
+
+    app.worker.send(Request::Load(pt.clone()));
+}
+
+/// Worker results for the current track; anything for an earlier track is dropped.
+fn apply_loaded(app: &mut AppState, loaded: Loaded) {
+    if loaded.track_id != app.track_id {
+        return;
+    }
 
     if app.is_kitty {
-        app.cover_art_kitty = art_img.as_ref().map(|img| {
+        app.cover_art_kitty = loaded.cover.as_ref().map(|img| {
             let resized = img.resize_exact(220, 160, image::imageops::FilterType::Lanczos3);
             let rgba = resized.to_rgba8();
             let (w, h) = (rgba.width(), rgba.height());
@@ -354,29 +491,404 @@ fn play_track(app: &mut AppState, idx: usize) {
         app.cover_art_lines = vec![];
     } else {
         app.cover_art_kitty = None;
-        app.cover_art_lines = art_img
+        app.cover_art_lines = loaded.cover
             .map(|img| render_cover_art_halfblock(&img, 22, 8))
             .unwrap_or_default();
     }
 
-    // fetch lyrics
-    match lyrics::fetch_lyrics(&pt.artist, &pt.title) {
+    match loaded.lyrics {
         Ok(ld) => {
-            app.raw_lyrics = ld.lines.clone();
-            app.synced = ld.synced.clone();
-            app.cached_lines = cache_lines(&app.raw_lyrics);
-            app.status = format!("Now playing: {} — {}", pt.artist, pt.title);
-            info!("Loaded lyrics for {}", pt.title);
+            apply_lyrics(app, ld);
+            info!("Loaded lyrics for {}", app.title);
         }
         Err(e) => {
             app.raw_lyrics = vec!["No lyrics found".into()];
             app.synced.clear();
+            app.lyrics_source = None;
+            app.instrumental = false;
+            app.parallel.clear();
             app.cached_lines = cache_lines(&app.raw_lyrics);
             app.status = format!("No lyrics ({})", e);
         }
     }
 }
 
+fn apply_lyrics(app: &mut AppState, ld: LyricsData) {
+    app.raw_lyrics = ld.lines;
+    app.synced = ld.synced;
+    app.lyrics_source = ld.source.map(|s| s.provider);
+    app.instrumental = ld.instrumental;
+    app.parallel = ld.parallel;
+    app.cached_lines = cache_lines(&app.raw_lyrics);
+    app.current_line = 0;
+    app.scroll = 0;
+    app.lyric_cursor = None;
+}
+
+fn current_track_query(app: &AppState) -> TrackQuery {
+    TrackQuery {
+        id: app.track_id.clone(),
+        artist: app.artist.clone(),
+        title: app.title.clone(),
+        album: app.album.clone(),
+        duration: app.duration_seconds,
+        ..Default::default()
+    }
+}
+
+/// Write the current synced lyrics to EXPORT_DIR in EXPORT_FORMAT.
+fn export_current_lyrics(app: &mut AppState) {
+    if app.title.is_empty() {
+        return;
+    }
+    let track = current_track_query(app);
+    let path = app.config.export_path().join(lyrics::export_file_name(&track, &app.config.export_format));
+    let ld = LyricsData { lines: app.raw_lyrics.clone(), synced: app.synced.clone(), ..Default::default() };
+    app.status = match lyrics::export_lyrics(&path, &ld, &track, app.lyrics_offset_ms) {
+        Ok(()) => format!("Exported {}", path.display()),
+        Err(e) => format!("Export failed ({})", e),
+    };
+}
+
+fn nudge_lyrics_offset(app: &mut AppState, delta_ms: i64) {
+    if app.title.is_empty() {
+        return;
+    }
+    app.lyrics_offset_ms += delta_ms;
+    lyrics::save_offset(&current_track_query(app), app.lyrics_offset_ms);
+    app.status = format!("Lyrics offset: {:+}ms", app.lyrics_offset_ms);
+}
+
+// ----------------------------------------
+// Lyrics search (F5) — pick a candidate by hand
+// ----------------------------------------
+fn open_lyrics_search(app: &mut AppState) {
+    app.view = AppView::LyricsSearch;
+    if app.title.is_empty() || app.picker_track_id == app.track_id {
+        return;
+    }
+    app.picker_track_id = app.track_id.clone();
+    app.picker_query = format!("{} {}", app.artist, app.title);
+    run_lyrics_search(app);
+}
+
+fn run_lyrics_search(app: &mut AppState) {
+    app.status = format!("Searching for \"{}\"…", app.picker_query);
+    app.worker.send(Request::Search { track: current_track_query(app), query: app.picker_query.clone() });
+}
+
+/// Search results, unless the query was edited or the track changed meanwhile.
+fn apply_searched(app: &mut AppState, track: TrackQuery, query: String, results: Result<Vec<(f32, Candidate)>, LyricsError>) {
+    if query != app.picker_query || track.id != app.picker_track_id {
+        return;
+    }
+    app.picker_cursor = 0;
+    match results {
+        Ok(results) => {
+            app.status = format!("{} results for \"{}\"", results.len(), query);
+            app.picker_results = results;
+        }
+        Err(e) => {
+            error!("Lyrics search: {}", e);
+            app.status = format!("Search failed ({})", e);
+            app.picker_results.clear();
+        }
+    }
+}
+
+fn pick_lyrics_candidate(app: &mut AppState) {
+    let Some((_, c)) = app.picker_results.get(app.picker_cursor) else { return };
+    let track = current_track_query(app);
+    let ld = lyrics::pin_lyrics(&app.lyrics_cache, &track, &c.lyrics);
+    info!("Pinned lyrics for {} — {}: {} — {}", track.artist, track.title, c.artist, c.title);
+    app.status = format!("Pinned lyrics: {} — {}", c.artist, c.title);
+    apply_lyrics(app, ld);
+    app.view = AppView::NowPlaying;
+}
+
+// ----------------------------------------
+// Lyrics cache (F6)
+// ----------------------------------------
+fn open_cache_view(app: &mut AppState) {
+    app.cache_entries = app.lyrics_cache.entries();
+    app.cache_cursor = app.cache_cursor.min(app.cache_entries.len().saturating_sub(1));
+    app.view = AppView::Cache;
+}
+
+fn delete_cache_entry(app: &mut AppState) {
+    let Some((key, e)) = app.cache_entries.get(app.cache_cursor) else { return };
+    app.lyrics_cache.remove(key);
+    app.status = format!("Removed cached lyrics: {} — {}", e.artist, e.title);
+    open_cache_view(app);
+}
+
+fn toggle_cache_pin(app: &mut AppState) {
+    let Some((key, e)) = app.cache_entries.get(app.cache_cursor) else { return };
+    let pin = !e.pinned;
+    app.status = if app.lyrics_cache.set_pinned(key, pin) {
+        format!("{} {} — {}", if pin { "Pinned" } else { "Unpinned" }, e.artist, e.title)
+    } else {
+        "Nothing to pin".into()
+    };
+    open_cache_view(app);
+}
+
+/// Drop the entry and ask the providers again; shows the result if it's the current track.
+fn refetch_cache_entry(app: &mut AppState) {
+    let Some((key, e)) = app.cache_entries.get(app.cache_cursor) else { return };
+    if !e.refetchable() {
+        app.status = format!("Can't refetch {}: no title to look up", key);
+        return;
+    }
+    app.status = format!("Refetching {} — {}…", e.artist, e.title);
+    app.worker.send(Request::Refetch { key: key.clone(), track: e.track() });
+}
+
+fn apply_refetched(app: &mut AppState, track: TrackQuery, res: Result<LyricsData, LyricsError>) {
+    match res {
+        Ok(ld) => {
+            app.status = format!("Refetched: {} — {}", track.artist, track.title);
+            let playing = current_track_query(app);
+            if (!track.id.is_empty() && track.id == playing.id)
+                || (track.artist == playing.artist && track.title == playing.title)
+            {
+                apply_lyrics(app, ld);
+            }
+        }
+        Err(e) => app.status = format!("Refetch failed ({})", e),
+    }
+    if app.view == AppView::Cache {
+        open_cache_view(app);
+    }
+}
+
+// ----------------------------------------
+// Timing editor (F7)
+// ----------------------------------------
+/// Starts from the synced lines if there are any (to fix them), otherwise from the
+/// plain lyrics. Edits are kept while the same track is playing.
+fn open_sync_editor(app: &mut AppState) {
+    app.view = AppView::SyncEditor;
+    if app.sync_track.id == app.track_id && !app.sync_lines.is_empty() {
+        return;
+    }
+    app.sync_track = current_track_query(app);
+    app.sync_cursor = 0;
+    app.sync_word = 0;
+    app.sync_lines = if !app.synced.is_empty() {
+        app.synced.iter().map(|l| {
+            let mut line = SyncLine::new(&l.text);
+            line.time_ms = Some(l.time_ms);
+            if !l.words.is_empty() {
+                line.words = l.words.iter().map(|w| (w.word.clone(), Some(w.time_ms))).collect();
+            }
+            line
+        }).collect()
+    } else if app.lyrics_source.is_some() && !app.instrumental {
+        app.raw_lyrics.iter().map(|l| l.trim()).filter(|l| !l.is_empty()).map(SyncLine::new).collect()
+    } else {
+        vec![]
+    };
+}
+
+/// Where the lyrics are right now, offset included, so taps line up with what is shown.
+fn lyrics_clock(app: &AppState) -> u32 {
+    ((playback_position(app) * 1000.0) as i64 + app.lyrics_offset_ms).max(0) as u32
+}
+
+/// Stamp the selected line (or its next word in word mode) and move on.
+fn sync_tap(app: &mut AppState) {
+    // the clock is the playing track's; it means nothing for the lines being timed
+    if app.sync_track.id != app.track_id {
+        app.status = "Another track is playing — s saves what's timed, F7 starts over".into();
+        return;
+    }
+    let ms = lyrics_clock(app);
+    let last = app.sync_lines.len().saturating_sub(1);
+    let Some(line) = app.sync_lines.get_mut(app.sync_cursor) else { return };
+
+    if app.sync_word_mode {
+        if app.sync_word == 0 {
+            line.time_ms = Some(ms);
+        }
+        if let Some((_, t)) = line.words.get_mut(app.sync_word) {
+            *t = Some(ms);
+        }
+        app.sync_word += 1;
+        if app.sync_word < line.words.len() {
+            return;
+        }
+    } else {
+        // retapping a line keeps its words where they were relative to it
+        let delta = line.time_ms.map_or(0, |old| ms as i64 - old as i64);
+        line.shift(delta);
+        line.time_ms = Some(ms);
+    }
+
+    app.sync_word = 0;
+    if app.sync_cursor == last {
+        app.status = "Last line timed — s to save".into();
+    }
+    app.sync_cursor = (app.sync_cursor + 1).min(last);
+}
+
+fn sync_nudge(app: &mut AppState, delta_ms: i64) {
+    let Some(line) = app.sync_lines.get_mut(app.sync_cursor) else { return };
+    if line.time_ms.is_some() {
+        line.shift(delta_ms);
+    }
+}
+
+/// Forget the selected line's timing and rewind to just before it to tap it again.
+fn sync_redo(app: &mut AppState) {
+    let Some(line) = app.sync_lines.get_mut(app.sync_cursor) else { return };
+    let old = line.time_ms.take();
+    for (_, t) in &mut line.words {
+        *t = None;
+    }
+    app.sync_word = 0;
+    let before = app.sync_lines[..app.sync_cursor].iter().rev().find_map(|l| l.time_ms);
+    if let Some(t) = old.or(before) {
+        let pos = (t as i64 - app.lyrics_offset_ms - 2000).max(0);
+        seek_to(app, pos as f32 / 1000.0);
+    }
+}
+
+fn save_sync(app: &mut AppState) {
+    let mut untimed = 0;
+    let synced: Vec<SyncedLine> = app.sync_lines.iter().filter_map(|l| {
+        let Some(time_ms) = l.time_ms else {
+            untimed += 1;
+            return None;
+        };
+        // word timings only when every word of the line has one
+        let words = l.words.iter()
+            .map(|(w, t)| Some(KaraokeWord { time_ms: (*t)?, end_ms: 0, word: w.clone() }))
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
+        Some(SyncedLine { time_ms, text: l.text.clone(), words })
+    }).collect();
+    if synced.is_empty() {
+        app.status = "Nothing timed yet — Enter at the start of each line".into();
+        return;
+    }
+
+    match lyrics::save_timed_lyrics(&app.config, &app.lyrics_cache, &app.sync_track, synced) {
+        Ok((path, ld)) => {
+            info!("Saved timed lyrics to {}", path.display());
+            app.status = if untimed > 0 {
+                format!("Saved {} ({} untimed lines left out)", path.display(), untimed)
+            } else {
+                format!("Saved {}", path.display())
+            };
+            if app.sync_track.id == app.track_id {
+                apply_lyrics(app, ld);
+            }
+        }
+        Err(e) => {
+            error!("Saving timed lyrics: {}", e);
+            app.status = format!("Save failed ({})", e);
+        }
+    }
+}
+
+// ----------------------------------------
+// Playback clock + seeking
+// ----------------------------------------
+
+/// Current position in seconds: mpv's time-pos when we own the player,
+/// otherwise estimated from when the track started.
+fn playback_position(app: &AppState) -> f32 {
+    if let Some(ref player) = app.player {
+        return player.state().position().unwrap_or(0.0).max(0.0) as f32;
+    }
+    if let Some(start) = app.start_timestamp_utc {
+        let diff = Utc::now().signed_duration_since(start).num_milliseconds();
+        (diff as f32 / 1000.0).clamp(0.0, app.duration_seconds as f32)
+    } else {
+        0.0
+    }
+}
+
+fn seek_to(app: &mut AppState, secs: f32) {
+    // the other client owns playback; moving our clock would only desync the lyrics
+    if app.config.follow_mode {
+        app.status = "Can't seek while following — seek on the playing client".into();
+        return;
+    }
+    let secs = if app.duration_seconds > 0 {
+        secs.clamp(0.0, app.duration_seconds as f32)
+    } else {
+        secs.max(0.0)
+    };
+
+    if let Some(ref mut player) = app.player {
+        if let Err(e) = player.seek(secs as f64) {
+            error!("Seek failed: {}", e);
+        }
+    }
+    // rebase the wall clock too; it is all we have without mpv
+    app.start_timestamp_utc = Some(Utc::now() - chrono::Duration::milliseconds((secs * 1000.0) as i64));
+
+    let s = secs as u32;
+    app.status = format!("Seek {:02}:{:02}", s / 60, s % 60);
+}
+
+fn seek_by(app: &mut AppState, delta: f32) {
+    let pos = playback_position(app);
+    seek_to(app, pos + delta);
+}
+
+// ----------------------------------------
+// Follow mode
+// ----------------------------------------
+// Polls Navidrome's getNowPlaying and mirrors whatever the configured user is
+// playing on another Subsonic client. No mpv is spawned in this mode.
+fn poll_now_playing(app: &mut AppState) {
+    app.follow_last_poll = Some(Instant::now());
+    app.worker.send(Request::NowPlaying);
+}
+
+fn apply_now_playing(app: &mut AppState, res: Result<Vec<NowPlaying>, NavidromeError>) {
+    // follow mode was switched off while the poll was out
+    if !app.config.follow_mode {
+        return;
+    }
+    let entries = match res {
+        Ok(e) => e,
+        Err(e) => {
+            error!("Now playing: {}", e);
+            app.status = format!("Now playing unavailable ({})", e);
+            return;
+        }
+    };
+
+    // A user may have several players open; the most recent one wins.
+    let mine = entries
+        .into_iter()
+        .filter(|np| np.username == app.config.navidrome_user)
+        .min_by_key(|np| np.minutes_ago);
+
+    match mine {
+        Some(np) if np.track.id != app.track_id => follow_track(app, np),
+        Some(_) => {}
+        None => {
+            if !app.track_id.is_empty() {
+                info!("Nothing playing for {}", app.config.navidrome_user);
+            }
+            app.status = format!("Following {} — nothing playing", app.config.navidrome_user);
+        }
+    }
+}
+
+fn follow_track(app: &mut AppState, np: NowPlaying) {
+    info!("Following now playing: {} — {}", np.track.artist, np.track.title);
+    // minutesAgo is whole minutes, so this is only a coarse estimate
+    let started = Utc::now() - chrono::Duration::minutes(np.minutes_ago as i64);
+    load_track(app, &np.track, started);
+    app.status = format!("Following: {} — {}", np.track.artist, np.track.title);
+}
+
 // ----------------------------------------
 // handle_track_end — YOUR CONTRIBUTION
 // ----------------------------------------
@@ -421,12 +933,28 @@ fn handle_track_end(app: &mut AppState) {
 // Main entry
 // ----------------------------------------
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // CLI runs add to the log of a TUI session that may still be going
+    let log_file = if args.is_empty() {
+        File::create("sonix_lyrics.log")?
+    } else {
+        OpenOptions::new().create(true).append(true).open("sonix_lyrics.log")?
+    };
     CombinedLogger::init(vec![WriteLogger::new(
         LevelFilter::Info,
         ConfigBuilder::new().build(),
-        File::create("sonix_lyrics.log")?,
+        log_file,
     )])?;
 
+    if !args.is_empty() {
+        if let Err(e) = cli::run(&args) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     info!("Sonix Lyrics starting…");
 
     let config = Config::load_or_setup();
@@ -463,34 +991,31 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, cfg: Config) -> io
         std::thread::sleep(Duration::from_millis(100));
     });
 
+    let replies_rx = app.worker.replies.clone();
     let mut last_draw = Instant::now();
 
     loop {
+        // attach to mpv once its IPC socket is up
+        if app.mpv_process.is_some() && app.player.is_none() {
+            app.player = MpvClient::connect(MPV_SOCK).ok();
+        }
+
         // playback clock
-        let pos = if let Some(start) = app.start_timestamp_utc {
-            let diff = Utc::now().signed_duration_since(start).num_milliseconds();
-            (diff as f32 / 1000.0).clamp(0.0, app.duration_seconds as f32)
-        } else {
-            0.0
-        };
+        let playback = app.player.as_ref().map(|p| p.state());
+        if let Some(ref st) = playback {
+            if let Some(d) = st.duration {
+                app.duration_seconds = d.round() as u32;
+            }
+            app.jukebox_playing = !st.paused;
+        }
+        let pos = playback_position(&app);
 
         app.progress_seconds = pos.floor() as u32;
         app.progress = if app.duration_seconds > 0 { pos / app.duration_seconds as f32 } else { 0.0 };
 
-        // karaoke word
-        app.current_word = None;
-        let ms = (pos * 1000.0) as u32;
-        if app.config.karaoke_enabled {
-            for line in &app.synced {
-                if line.time_ms <= ms {
-                    for w in &line.words {
-                        if w.time_ms <= ms {
-                            app.current_word = Some(w.word.clone());
-                        }
-                    }
-                }
-            }
-        }
+        // lyrics clock, with the per-track offset applied
+        let ms = ((pos * 1000.0) as i64 + app.lyrics_offset_ms).max(0) as u32;
+        app.lyrics_ms = ms;
 
         // auto scroll
         if !app.synced.is_empty() {
@@ -500,21 +1025,41 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, cfg: Config) -> io
             }
             if idx as u16 != app.current_line {
                 app.current_line = idx as u16;
-                app.scroll = app.current_line.saturating_sub(5);
+                // leave the view alone while a line is being picked
+                if app.lyric_cursor.is_none() {
+                    app.scroll = lyric_row(&app, app.current_line as usize).saturating_sub(5);
+                }
+            }
+        }
+
+        // karaoke word within the current line
+        app.current_word = None;
+        if app.config.karaoke_enabled {
+            if let Some(line) = app.synced.get(app.current_line as usize).filter(|l| l.time_ms <= ms) {
+                app.current_word = line.words.iter().rposition(|w| w.time_ms <= ms);
             }
         }
 
         // reap mpv and trigger loop logic
+        let eof = playback.as_ref().is_some_and(|st| st.eof_reached);
         let mpv_finished = if let Some(ref mut child) = app.mpv_process {
-            matches!(child.try_wait(), Ok(Some(_)))
+            eof || matches!(child.try_wait(), Ok(Some(_)))
         } else { false };
 
         if mpv_finished {
-            app.mpv_process = None;
+            kill_mpv(&mut app);
             app.jukebox_playing = false;
             handle_track_end(&mut app);
         }
 
+        // follow mode: poll getNowPlaying every refresh_interval seconds
+        if app.config.follow_mode {
+            let every = Duration::from_secs(app.config.refresh_interval.max(1));
+            if app.follow_last_poll.is_none_or(|t| t.elapsed() >= every) {
+                poll_now_playing(&mut app);
+            }
+        }
+
         if last_draw.elapsed() >= Duration::from_millis(33) {
             terminal.draw(|f| ui(f, &app))?;
             // Kitty image must be resent after every draw (Ratatui cell writes erase it)
@@ -530,6 +1075,13 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, cfg: Config) -> io
 
         select! {
             recv(tick_rx) -> _ => {},
+            recv(replies_rx) -> msg => match msg {
+                Ok(Reply::Loaded(loaded)) => apply_loaded(&mut app, loaded),
+                Ok(Reply::Searched { track, query, results }) => apply_searched(&mut app, track, query, results),
+                Ok(Reply::Refetched { track, lyrics }) => apply_refetched(&mut app, track, lyrics),
+                Ok(Reply::NowPlaying(res)) => apply_now_playing(&mut app, res),
+                Err(_) => {}
+            },
             default(Duration::from_millis(10)) => {
                 if event::poll(Duration::from_millis(10))? {
                     if let Event::Key(key) = event::read()? {
@@ -558,15 +1110,34 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, cfg: Config) -> io
                             continue;
                         }
 
+                        // lyrics search box eats all input too
+                        if app.picker_editing {
+                            match key.code {
+                                KeyCode::Esc => { app.picker_editing = false; }
+                                KeyCode::Enter => {
+                                    app.picker_editing = false;
+                                    run_lyrics_search(&mut app);
+                                }
+                                KeyCode::Backspace => { app.picker_query.pop(); }
+                                KeyCode::Char(c) => { app.picker_query.push(c); }
+                                _ => {}
+                            }
+                            continue;
+                        }
+
                         match key.code {
                             // ---- global ----
                             KeyCode::Char('q') => {
-                                kill_mpv(&mut app.mpv_process);
+                                kill_mpv(&mut app);
                                 return Ok(());
                             }
                             KeyCode::Char('?') => { app.show_help = !app.show_help; }
                             KeyCode::Esc => {
                                 if app.show_help { app.show_help = false; }
+                                else if app.lyric_cursor.is_some() {
+                                    app.lyric_cursor = None;
+                                    app.scroll = lyric_row(&app, app.current_line as usize).saturating_sub(5);
+                                }
                                 else if app.view != AppView::NowPlaying {
                                     app.view = AppView::NowPlaying;
                                 }
@@ -592,16 +1163,29 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, cfg: Config) -> io
                                 app.settings_cursor = 0;
                                 app.view = AppView::Settings;
                             }
+                            KeyCode::F(5) => open_lyrics_search(&mut app),
+                            KeyCode::F(6) => open_cache_view(&mut app),
+                            KeyCode::F(7) => open_sync_editor(&mut app),
+                            KeyCode::Char('w') if app.view == AppView::SyncEditor => {
+                                app.sync_word_mode = !app.sync_word_mode;
+                                app.sync_word = 0;
+                                app.status = format!("Word timing {}", if app.sync_word_mode { "on" } else { "off" });
+                            }
+                            KeyCode::Char('s') if app.view == AppView::SyncEditor => save_sync(&mut app),
+                            KeyCode::Char('}') if app.view == AppView::SyncEditor => sync_nudge(&mut app, -100),
+                            KeyCode::Char('{') if app.view == AppView::SyncEditor => sync_nudge(&mut app, 100),
+                            KeyCode::Backspace if app.view == AppView::SyncEditor => sync_redo(&mut app),
+                            KeyCode::Char('d') if app.view == AppView::Cache => delete_cache_entry(&mut app),
+                            KeyCode::Char('r') if app.view == AppView::Cache => refetch_cache_entry(&mut app),
+                            KeyCode::Char('/') if app.view == AppView::LyricsSearch => {
+                                app.picker_editing = true;
+                            }
 
                             // ---- playback ----
                             KeyCode::Char(' ') => {
-                                if app.jukebox_playing {
-                                    mpv_ipc("{\"command\":[\"set_property\",\"pause\",true]}\n");
-                                    app.jukebox_playing = false;
-                                } else {
-                                    mpv_ipc("{\"command\":[\"set_property\",\"pause\",false]}\n");
-                                    app.jukebox_playing = true;
-                                }
+                                let pause = app.jukebox_playing;
+                                app.mpv_command(json!(["set_property", "pause", pause]));
+                                app.jukebox_playing = !pause;
                             }
                             KeyCode::Char(']') => {
                                 let idx = (app.jukebox_index + 1) as usize;
@@ -613,22 +1197,65 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, cfg: Config) -> io
                             }
                             KeyCode::Char('+') | KeyCode::Char('=') => {
                                 let gain = (app.jukebox_gain + 0.1).min(1.0);
-                                mpv_ipc(&format!("{{\"command\":[\"set_property\",\"volume\",{}]}}\n", (gain * 100.0).round() as u32));
+                                app.mpv_command(json!(["set_property", "volume", (gain * 100.0).round() as u32]));
                                 app.jukebox_gain = gain;
                             }
                             KeyCode::Char('-') => {
                                 let gain = (app.jukebox_gain - 0.1).max(0.0);
-                                mpv_ipc(&format!("{{\"command\":[\"set_property\",\"volume\",{}]}}\n", (gain * 100.0).round() as u32));
+                                app.mpv_command(json!(["set_property", "volume", (gain * 100.0).round() as u32]));
                                 app.jukebox_gain = gain;
                             }
                             KeyCode::Char('l') => {
                                 app.loop_mode = app.loop_mode.next();
                                 app.status = format!("Loop: {}", app.loop_mode.label());
                             }
+                            KeyCode::Right | KeyCode::Char('.') => seek_by(&mut app, 5.0),
+                            KeyCode::Left | KeyCode::Char(',') => seek_by(&mut app, -5.0),
+                            KeyCode::Char('>') => seek_by(&mut app, 30.0),
+                            KeyCode::Char('<') => seek_by(&mut app, -30.0),
+                            // jump to 0–90%; only where the digits can't mean anything else
+                            KeyCode::Char(c @ '0'..='9') if app.view == AppView::NowPlaying => {
+                                let pct = c.to_digit(10).unwrap_or(0) as f32 / 10.0;
+                                let target = app.duration_seconds as f32 * pct;
+                                seek_to(&mut app, target);
+                            }
+                            KeyCode::Char('}') => nudge_lyrics_offset(&mut app, 100),
+                            KeyCode::Char('{') => nudge_lyrics_offset(&mut app, -100),
+                            KeyCode::Char('e') => export_current_lyrics(&mut app),
+                            KeyCode::Char('t') => {
+                                app.config.show_translations = !app.config.show_translations;
+                                app.scroll = lyric_row(&app, app.current_line as usize).saturating_sub(5);
+                                app.status = if app.parallel.is_empty() {
+                                    "No translation for these lyrics".into()
+                                } else if app.config.show_translations {
+                                    "Translations on".into()
+                                } else {
+                                    "Translations off".into()
+                                };
+                            }
+                            KeyCode::Char('f') => {
+                                let on = !app.config.follow_mode;
+                                app.set_follow_mode(on);
+                                app.status = if on {
+                                    format!("Follow mode: tracking {}", app.config.navidrome_user)
+                                } else {
+                                    "Follow mode off".into()
+                                };
+                            }
+                            KeyCode::Char('r') if app.config.follow_mode => {
+                                app.track_id.clear();
+                                poll_now_playing(&mut app);
+                            }
 
                             // ---- navigation (view-specific) ----
                             KeyCode::Down | KeyCode::Char('j') => {
                                 match app.view {
+                                    AppView::NowPlaying if !app.synced.is_empty() => {
+                                        let cur = app.lyric_cursor.unwrap_or(app.current_line as usize);
+                                        let next = (cur + 1).min(app.synced.len() - 1);
+                                        app.lyric_cursor = Some(next);
+                                        app.scroll = lyric_row(&app, next).saturating_sub(5);
+                                    }
                                     AppView::NowPlaying => { app.scroll += 1; }
                                     AppView::Queue => {
                                         if app.queue_cursor + 1 < app.tracks.len() {
@@ -648,12 +1275,34 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, cfg: Config) -> io
                                         }
                                     },
                                     AppView::Settings => {
-                                        if app.settings_cursor < 3 { app.settings_cursor += 1; }
+                                        if app.settings_cursor < 4 { app.settings_cursor += 1; }
+                                    }
+                                    AppView::LyricsSearch => {
+                                        if app.picker_cursor + 1 < app.picker_results.len() {
+                                            app.picker_cursor += 1;
+                                        }
+                                    }
+                                    AppView::Cache => {
+                                        if app.cache_cursor + 1 < app.cache_entries.len() {
+                                            app.cache_cursor += 1;
+                                        }
+                                    }
+                                    AppView::SyncEditor => {
+                                        if app.sync_cursor + 1 < app.sync_lines.len() {
+                                            app.sync_cursor += 1;
+                                            app.sync_word = 0;
+                                        }
                                     }
                                 }
                             }
                             KeyCode::Up | KeyCode::Char('k') => {
                                 match app.view {
+                                    AppView::NowPlaying if !app.synced.is_empty() => {
+                                        let cur = app.lyric_cursor.unwrap_or(app.current_line as usize);
+                                        let prev = cur.saturating_sub(1);
+                                        app.lyric_cursor = Some(prev);
+                                        app.scroll = lyric_row(&app, prev).saturating_sub(5);
+                                    }
                                     AppView::NowPlaying => { app.scroll = app.scroll.saturating_sub(1); }
                                     AppView::Queue => { app.queue_cursor = app.queue_cursor.saturating_sub(1); }
                                     AppView::Playlists => match app.playlist_focus {
@@ -661,25 +1310,41 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, cfg: Config) -> io
                                         PlaylistFocus::Tracks => { app.track_cursor = app.track_cursor.saturating_sub(1); }
                                     },
                                     AppView::Settings => { app.settings_cursor = app.settings_cursor.saturating_sub(1); }
+                                    AppView::LyricsSearch => { app.picker_cursor = app.picker_cursor.saturating_sub(1); }
+                                    AppView::Cache => { app.cache_cursor = app.cache_cursor.saturating_sub(1); }
+                                    AppView::SyncEditor => {
+                                        app.sync_cursor = app.sync_cursor.saturating_sub(1);
+                                        app.sync_word = 0;
+                                    }
                                 }
                             }
 
-                            KeyCode::Tab => {
-                                if app.view == AppView::Playlists {
-                                    app.playlist_focus = match app.playlist_focus {
-                                        PlaylistFocus::Playlists => PlaylistFocus::Tracks,
-                                        PlaylistFocus::Tracks => PlaylistFocus::Playlists,
-                                    };
-                                }
+                            KeyCode::Tab if app.view == AppView::Playlists => {
+                                app.playlist_focus = match app.playlist_focus {
+                                    PlaylistFocus::Playlists => PlaylistFocus::Tracks,
+                                    PlaylistFocus::Tracks => PlaylistFocus::Playlists,
+                                };
                             }
 
                             KeyCode::Enter => {
                                 match app.view {
+                                    AppView::NowPlaying => {
+                                        // jump to the selected lyric line
+                                        if let Some(sel) = app.lyric_cursor.take() {
+                                            if let Some(t) = app.synced.get(sel).map(|l| l.time_ms) {
+                                                seek_to(&mut app, t as f32 / 1000.0);
+                                                app.scroll = lyric_row(&app, sel).saturating_sub(5);
+                                            }
+                                        }
+                                    }
                                     AppView::Queue => {
                                         let idx = app.queue_cursor;
                                         play_track(&mut app, idx);
                                         app.view = AppView::NowPlaying;
                                     }
+                                    AppView::LyricsSearch => pick_lyrics_candidate(&mut app),
+                                    AppView::Cache => toggle_cache_pin(&mut app),
+                                    AppView::SyncEditor => sync_tap(&mut app),
                                     AppView::Playlists => match app.playlist_focus {
                                         PlaylistFocus::Playlists => {
                                             if let Some(pl) = app.playlists.get(app.playlist_cursor) {
@@ -701,7 +1366,7 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, cfg: Config) -> io
                                     },
                                     AppView::Settings => {
                                         match app.settings_cursor {
-                                            0 | 1 | 2 => {
+                                            0..=2 => {
                                                 // start editing text field
                                                 app.settings_editing = true;
                                                 app.settings_buf = match app.settings_cursor {
@@ -712,10 +1377,10 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, cfg: Config) -> io
                                                 };
                                             }
                                             3 => { app.settings_karaoke = !app.settings_karaoke; }
+                                            4 => { app.settings_follow = !app.settings_follow; }
                                             _ => {}
                                         }
                                     }
-                                    _ => {}
                                 }
                             }
 
@@ -767,6 +1432,9 @@ fn ui(f: &mut Frame, app: &AppState) {
         AppView::Queue => render_queue(f, chunks[1], app),
         AppView::Playlists => render_playlists(f, chunks[1], app),
         AppView::Settings => render_settings(f, chunks[1], app),
+        AppView::LyricsSearch => render_lyrics_search(f, chunks[1], app),
+        AppView::Cache => render_cache(f, chunks[1], app),
+        AppView::SyncEditor => render_sync_editor(f, chunks[1], app),
     }
 
     if app.show_help { render_help(f); }
@@ -778,6 +1446,9 @@ fn render_tab_bar(f: &mut Frame, area: Rect, app: &AppState) {
         (AppView::Queue,      "F2 Queue"),
         (AppView::Playlists,  "F3 Playlists"),
         (AppView::Settings,   "F4 Settings"),
+        (AppView::LyricsSearch, "F5 Lyrics"),
+        (AppView::Cache,      "F6 Cache"),
+        (AppView::SyncEditor, "F7 Sync"),
     ];
 
     let mut spans: Vec<Span<'static>> = vec![Span::raw(" ")];
@@ -795,11 +1466,27 @@ fn render_tab_bar(f: &mut Frame, area: Rect, app: &AppState) {
     // Loop indicator
     if app.loop_mode != LoopMode::Off {
         spans.push(Span::styled(
-            format!("↻ {}", app.loop_mode.label()),
+            format!("↻ {}  ", app.loop_mode.label()),
             Style::default().fg(Color::Cyan),
         ));
     }
 
+    // Per-track lyrics offset
+    if app.lyrics_offset_ms != 0 {
+        spans.push(Span::styled(
+            format!("⏱ {:+}ms  ", app.lyrics_offset_ms),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+
+    // Follow mode indicator
+    if app.config.follow_mode {
+        spans.push(Span::styled(
+            format!("◉ Following {}", app.config.navidrome_user),
+            Style::default().fg(Color::Green),
+        ));
+    }
+
     f.render_widget(Paragraph::new(Line::from(spans)), area);
 }
 
@@ -874,7 +1561,9 @@ fn render_left(f: &mut Frame, area: Rect, app: &AppState) {
             )));
         }
 
-        if let Some(ref w) = app.current_word {
+        let sung = app.current_word.and_then(|i| app.synced.get(app.current_line as usize)?.words.get(i));
+        if let Some(w) = sung {
+            let w = w.word.trim();
             lines.push(Line::default());
             lines.push(Line::from(Span::styled(
                 format!("♪ {} ♪", w),
@@ -901,43 +1590,135 @@ fn render_left(f: &mut Frame, area: Rect, app: &AppState) {
 fn render_lyrics(app: &AppState) -> Paragraph<'static> {
     let current = app.current_line as usize;
 
-    let lines: Vec<Line<'static>> = if !app.synced.is_empty() {
-        app.synced.iter().enumerate().map(|(i, sl)| {
-            let is_cur = i == current;
-            if is_cur && app.config.karaoke_enabled {
-                if let Some(ref cw) = app.current_word {
-                    let text = sl.text.clone();
-                    if let Some(pos) = text.find(cw.as_str()) {
-                        let before = text[..pos].to_string();
-                        let word   = text[pos..pos + cw.len()].to_string();
-                        let after  = text[pos + cw.len()..].to_string();
-                        return Line::from(vec![
-                            Span::styled(before, Style::default().fg(Color::DarkGray)),
-                            Span::styled(word, Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
-                            Span::styled(after, Style::default().fg(Color::DarkGray)),
-                        ]);
-                    }
-                }
-                Line::from(Span::styled(sl.text.clone(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)))
-            } else if is_cur {
-                Line::from(Span::styled(sl.text.clone(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)))
-            } else if i > current && i <= current + 3 {
-                Line::from(Span::styled(sl.text.clone(), Style::default().fg(Color::Gray)))
+    let lines: Vec<Line<'static>> = if app.instrumental {
+        vec![
+            Line::default(),
+            Line::default(),
+            Line::from(Span::styled(
+                "♪ Instrumental ♪",
+                Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+            )).centered(),
+            Line::default(),
+            Line::from(Span::styled("No vocals on this one.", Style::default().fg(Color::DarkGray))).centered(),
+        ]
+    } else if !app.synced.is_empty() {
+        let mut lines = Vec::with_capacity(app.synced.len());
+        for (i, sl) in app.synced.iter().enumerate() {
+            let line = lyric_line(app, i, sl, current);
+            lines.push(if app.lyric_cursor == Some(i) {
+                line.patch_style(Style::default().add_modifier(Modifier::REVERSED))
             } else {
-                Line::from(Span::styled(sl.text.clone(), Style::default().fg(Color::DarkGray)))
+                line
+            });
+            for text in parallel_rows(app, i) {
+                lines.push(Line::from(Span::styled(
+                    text.to_string(),
+                    Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+                )));
             }
-        }).collect()
+        }
+        lines
     } else {
         app.cached_lines.clone()
     };
 
+    let mut title = match app.lyrics_source {
+        Some(ref src) => format!("Lyrics ({})", src),
+        None => "Lyrics".to_string(),
+    };
+    if app.lyric_cursor.is_some() {
+        title.push_str("  [Enter=jump  Esc=cancel]");
+    }
+
     Paragraph::new(lines)
-        .block(Block::default().borders(Borders::ALL).title("Lyrics")
+        .block(Block::default().borders(Borders::ALL).title(title)
             .border_style(Style::default().fg(Color::Blue)))
         .scroll((app.scroll, 0))
         .wrap(Wrap { trim: false })
 }
 
+/// Translation / romanization text to show under synced line `i`.
+fn parallel_rows(app: &AppState, i: usize) -> impl Iterator<Item = &str> {
+    app.parallel
+        .iter()
+        .filter(|_| app.config.show_translations)
+        .filter_map(move |p| p.lines.get(i).map(String::as_str))
+        .filter(|t| !t.trim().is_empty())
+}
+
+/// Row of synced line `i` in the lyrics pane, counting the parallel rows above it.
+fn lyric_row(app: &AppState, i: usize) -> u16 {
+    (i + (0..i.min(app.synced.len())).map(|j| parallel_rows(app, j).count()).sum::<usize>()) as u16
+}
+
+fn lyric_line(app: &AppState, i: usize, sl: &SyncedLine, current: usize) -> Line<'static> {
+    let is_cur = i == current;
+    if is_cur && app.config.karaoke_enabled && !sl.words.is_empty() {
+        karaoke_line(app, sl)
+    } else if is_cur {
+        Line::from(Span::styled(sl.text.clone(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)))
+    } else if i > current && i <= current + 3 {
+        Line::from(Span::styled(sl.text.clone(), Style::default().fg(Color::Gray)))
+    } else {
+        Line::from(Span::styled(sl.text.clone(), Style::default().fg(Color::DarkGray)))
+    }
+}
+
+/// The current line built from its words: sung words, the word being sung filled
+/// up to the playback position, then the words still to come.
+fn karaoke_line(app: &AppState, sl: &SyncedLine) -> Line<'static> {
+    let sung = Style::default().fg(Color::Magenta);
+    let filling = Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD);
+    let pending = Style::default().fg(Color::White).add_modifier(Modifier::BOLD);
+    let upcoming = Style::default().fg(Color::Yellow);
+
+    let spans: Vec<Span<'static>> = karaoke_parts(&sl.words, app.current_word, app.lyrics_ms)
+        .into_iter()
+        .map(|(text, part)| {
+            let style = match part {
+                WordPart::Sung => sung,
+                WordPart::Filling => filling,
+                WordPart::Pending => pending,
+                WordPart::Upcoming => upcoming,
+            };
+            Span::styled(text, style)
+        })
+        .collect();
+    Line::from(spans)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WordPart {
+    Sung,
+    /// The sung part of the current word.
+    Filling,
+    /// The rest of the current word.
+    Pending,
+    Upcoming,
+}
+
+/// `words` cut up for highlighting at `ms`, with `current` the word being sung.
+fn karaoke_parts(words: &[KaraokeWord], current: Option<usize>, ms: u32) -> Vec<(String, WordPart)> {
+    let mut parts = Vec::with_capacity(words.len() + 1);
+    for (j, w) in words.iter().enumerate() {
+        match current {
+            Some(cur) if j < cur => parts.push((w.word.clone(), WordPart::Sung)),
+            Some(cur) if j == cur => {
+                let span = w.end_ms.saturating_sub(w.time_ms).max(1) as f32;
+                let frac = (ms.saturating_sub(w.time_ms) as f32 / span).clamp(0.0, 1.0);
+                // fill by characters of the word itself, not the spaces after it
+                let visible = w.word.trim_end();
+                let n = (visible.chars().count() as f32 * frac).round() as usize;
+                let split = visible.char_indices().nth(n).map(|(b, _)| b).unwrap_or(visible.len());
+                parts.push((w.word[..split].to_string(), WordPart::Filling));
+                parts.push((w.word[split..].to_string(), WordPart::Pending));
+            }
+            _ => parts.push((w.word.clone(), WordPart::Upcoming)),
+        }
+    }
+    parts
+}
+
 // ----------------------------------------
 // Queue view (F2)
 // ----------------------------------------
@@ -1054,6 +1835,7 @@ fn render_settings(f: &mut Frame, area: Rect, app: &AppState) {
         ("Username",          app.settings_user.clone(),    true),
         ("Refresh Interval",  app.settings_refresh.clone(), true),
         ("Karaoke Mode",      if app.settings_karaoke { "Enabled".into() } else { "Disabled".into() }, false),
+        ("Follow Now Playing", if app.settings_follow { "Enabled".into() } else { "Disabled".into() }, false),
     ];
 
     let mut lines: Vec<Line> = vec![
@@ -1115,26 +1897,215 @@ fn render_settings(f: &mut Frame, area: Rect, app: &AppState) {
     );
 }
 
+// ----------------------------------------
+// Lyrics search view (F5)
+// ----------------------------------------
+fn render_lyrics_search(f: &mut Frame, area: Rect, app: &AppState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+
+    let query = if app.picker_editing {
+        format!("{}█", app.picker_query)
+    } else {
+        app.picker_query.clone()
+    };
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(" Search: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(query, if app.picker_editing {
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Cyan)
+            }),
+        ]))
+        .block(Block::default().borders(Borders::ALL).title("/=edit  Enter=search")
+            .border_style(if app.picker_editing { Style::default().fg(Color::Yellow) } else { Style::default().fg(Color::DarkGray) })),
+        chunks[0],
+    );
+
+    let lines: Vec<Line> = if app.title.is_empty() {
+        vec![Line::from(Span::styled("Nothing playing.", Style::default().fg(Color::DarkGray)))]
+    } else if app.picker_results.is_empty() {
+        vec![Line::from(Span::styled("No results — press / to change the search.", Style::default().fg(Color::DarkGray)))]
+    } else {
+        app.picker_results.iter().enumerate().map(|(i, (score, c))| {
+            let flags = if c.instrumental {
+                "instrumental"
+            } else if !c.lyrics.synced.is_empty() {
+                "synced"
+            } else if !c.lyrics.lines.is_empty() {
+                "plain"
+            } else {
+                "empty"
+            };
+            let label = format!(
+                " {:.2}  {} — {} · {} ({:02}:{:02}) [{}]",
+                score, c.artist, c.title, c.album, c.duration / 60, c.duration % 60, flags
+            );
+            if i == app.picker_cursor {
+                Line::from(Span::styled(label, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)))
+            } else {
+                Line::from(Span::raw(label))
+            }
+        }).collect()
+    };
+
+    let scroll = if app.picker_cursor > 5 { (app.picker_cursor - 5) as u16 } else { 0 };
+    let title = format!(
+        "Lyrics for {} — {} ({:02}:{:02})  [Enter=use & pin]",
+        app.artist, app.title, app.duration_seconds / 60, app.duration_seconds % 60
+    );
+
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(title)
+                .border_style(Style::default().fg(Color::Blue)))
+            .scroll((scroll, 0)),
+        chunks[1],
+    );
+}
+
+// ----------------------------------------
+// Lyrics cache view (F6)
+// ----------------------------------------
+fn render_cache(f: &mut Frame, area: Rect, app: &AppState) {
+    let lines: Vec<Line> = if app.cache_entries.is_empty() {
+        vec![Line::from(Span::styled("The lyrics cache is empty.", Style::default().fg(Color::DarkGray)))]
+    } else {
+        app.cache_entries.iter().enumerate().map(|(i, (key, e))| {
+            let label = format!(
+                " {:<12} {:<9} {:>4}  {}{} — {}",
+                e.kind(),
+                e.provider.as_deref().unwrap_or("-"),
+                e.age(),
+                if e.pinned { "📌 " } else { "" },
+                e.artist,
+                if e.title.is_empty() { key } else { &e.title }
+            );
+            let style = if i == app.cache_cursor {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else if e.not_found {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(label, style))
+        }).collect()
+    };
+
+    let scroll = if app.cache_cursor > 5 { (app.cache_cursor - 5) as u16 } else { 0 };
+    let title = format!(
+        "Lyrics cache ({} entries)  [Enter=pin/unpin  r=refetch  d=delete]",
+        app.cache_entries.len()
+    );
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(title)
+                .border_style(Style::default().fg(Color::Blue)))
+            .scroll((scroll, 0)),
+        area,
+    );
+}
+
+// ----------------------------------------
+// Timing editor (F7)
+// ----------------------------------------
+fn render_sync_editor(f: &mut Frame, area: Rect, app: &AppState) {
+    let lines: Vec<Line> = if app.sync_lines.is_empty() {
+        vec![Line::from(Span::styled(
+            "No lyrics to time — pick some with F5, or put a .txt in LOCAL_LYRICS_DIR.",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        app.sync_lines.iter().enumerate().map(|(i, l)| {
+            let ts = match l.time_ms {
+                Some(t) => format!("{:02}:{:02}.{:02}", t / 60_000, t / 1000 % 60, t % 1000 / 10),
+                None => "--:--.--".to_string(),
+            };
+            let selected = i == app.sync_cursor;
+            let mut spans = vec![Span::styled(
+                format!("{} {}  ", if selected { "▶" } else { " " }, ts),
+                Style::default().fg(if l.time_ms.is_some() { Color::Cyan } else { Color::DarkGray }),
+            )];
+            if selected && app.sync_word_mode {
+                for (j, (w, t)) in l.words.iter().enumerate() {
+                    let style = if j == app.sync_word {
+                        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+                    } else if t.is_some() {
+                        Style::default().fg(Color::Magenta)
+                    } else {
+                        Style::default().fg(Color::White)
+                    };
+                    spans.push(Span::styled(w.clone(), style));
+                }
+            } else {
+                let style = if selected {
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else if l.time_ms.is_some() {
+                    Style::default()
+                } else {
+                    Style::default().fg(Color::Gray)
+                };
+                spans.push(Span::styled(l.text.clone(), style));
+            }
+            Line::from(spans)
+        }).collect()
+    };
+
+    let timed = app.sync_lines.iter().filter(|l| l.time_ms.is_some()).count();
+    let now = lyrics_clock(app);
+    let title = format!(
+        "Timing {} — {}{}  {:02}:{:02}.{}  {}/{} timed{}  [Enter=tap  w=words  {{/}}=nudge  ⌫=redo  s=save]",
+        app.sync_track.artist, app.sync_track.title,
+        if app.sync_track.id != app.track_id { " (not playing)" } else { "" },
+        now / 60_000, now / 1000 % 60, now % 1000 / 100,
+        timed, app.sync_lines.len(),
+        if app.sync_word_mode { "  (word mode)" } else { "" }
+    );
+    let scroll = if app.sync_cursor > 5 { (app.sync_cursor - 5) as u16 } else { 0 };
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(title)
+                .border_style(Style::default().fg(Color::Blue)))
+            .scroll((scroll, 0)),
+        area,
+    );
+}
+
 // ----------------------------------------
 // Help overlay
 // ----------------------------------------
 fn render_help(f: &mut Frame) {
-    let popup = centered_rect(50, 20, f.area());
+    let popup = centered_rect(50, 33, f.area());
     let help = vec![
         Line::from(Span::styled(" Views", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
         Line::from("  F1          Now Playing"),
         Line::from("  F2          Queue"),
         Line::from("  F3 / p      Playlists"),
         Line::from("  F4          Settings"),
+        Line::from("  F5          Lyrics search / pick"),
+        Line::from("  F6          Lyrics cache (d/r/Enter)"),
+        Line::from("  F7          Timing editor (Enter taps)"),
         Line::default(),
         Line::from(Span::styled(" Playback", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
         Line::from("  Space       Play / Pause"),
         Line::from("  ] / [       Next / Previous"),
         Line::from("  + / -       Volume"),
+        Line::from("  ← / → , .   Seek -5s / +5s"),
+        Line::from("  < / >       Seek -30s / +30s"),
+        Line::from("  0-9         Seek to 0%-90%"),
+        Line::from("  { / }       Lyrics later / earlier 100ms"),
+        Line::from("  e           Export lyrics (EXPORT_FORMAT)"),
+        Line::from("  t           Show / hide translations"),
         Line::from("  l           Cycle loop mode"),
+        Line::from("  f           Follow Navidrome now playing"),
+        Line::from("  r           Refresh now playing"),
         Line::default(),
         Line::from(Span::styled(" Navigation", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
-        Line::from("  j / k ↑↓    Scroll / navigate"),
+        Line::from("  j / k ↑↓    Scroll / pick lyric line"),
+        Line::from("  Enter       Jump to picked lyric line"),
         Line::from("  Tab         Switch pane (playlists)"),
         Line::from("  Enter       Select / play / edit"),
         Line::from("  q           Quit"),
@@ -1155,3 +2126,42 @@ fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let y = area.y + area.height.saturating_sub(height) / 2;
     Rect { x, y, width: width.min(area.width), height: height.min(area.height) }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(spec: &[(u32, u32, &str)]) -> Vec<KaraokeWord> {
+        spec.iter().map(|&(time_ms, end_ms, w)| KaraokeWord { time_ms, end_ms, word: w.into() }).collect()
+    }
+
+    fn parts(p: &[(&str, WordPart)]) -> Vec<(String, WordPart)> {
+        p.iter().map(|&(t, part)| (t.to_string(), part)).collect()
+    }
+
+    #[test]
+    fn karaoke_fills_the_current_word() {
+        use WordPart::*;
+        let line = words(&[(1000, 2000, "Hello "), (2000, 3000, "world")]);
+
+        // before the first word
+        assert_eq!(karaoke_parts(&line, None, 500), parts(&[("Hello ", Upcoming), ("world", Upcoming)]));
+        // the trailing space is not counted, so 3 of 5 letters at 50%
+        assert_eq!(
+            karaoke_parts(&line, Some(0), 1500),
+            parts(&[("Hel", Filling), ("lo ", Pending), ("world", Upcoming)])
+        );
+        assert_eq!(karaoke_parts(&line, Some(1), 2000), parts(&[("Hello ", Sung), ("", Filling), ("world", Pending)]));
+        // past the end of the last word it stays full
+        assert_eq!(karaoke_parts(&line, Some(1), 9000), parts(&[("Hello ", Sung), ("world", Filling), ("", Pending)]));
+    }
+
+    #[test]
+    fn karaoke_splits_multibyte_words_on_chars() {
+        use WordPart::*;
+        let line = words(&[(0, 1000, "夜に駆ける"), (1000, 1000, "👋🏽!")]);
+        assert_eq!(karaoke_parts(&line, Some(0), 400), parts(&[("夜に", Filling), ("駆ける", Pending), ("👋🏽!", Upcoming)]));
+        // a word with no length is filled at once
+        assert_eq!(karaoke_parts(&line, Some(1), 1001), parts(&[("夜に駆ける", Sung), ("👋🏽!", Filling), ("", Pending)]));
+    }
+}
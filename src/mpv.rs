@@ -0,0 +1,214 @@
+// src/mpv.rs
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::net::Shutdown;
+use std::os::unix::net::UnixStream;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum MpvError {
+    #[error("IPC: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Properties we ask mpv to push to us. The observe id is the index + 1.
+const OBSERVED: [&str; 5] = ["time-pos", "pause", "duration", "eof-reached", "paused-for-cache"];
+
+/// How far past the last time-pos update the clock may run on its own.
+/// mpv stops reporting while stalled, so this bounds the drift.
+const MAX_EXTRAPOLATION: f64 = 1.0;
+
+#[derive(Debug, Clone, Default)]
+pub struct PlaybackState {
+    pub time_pos: Option<f64>,
+    pub paused: bool,
+    pub buffering: bool,
+    pub duration: Option<f64>,
+    pub eof_reached: bool,
+    updated: Option<Instant>,
+}
+
+impl PlaybackState {
+    /// Current position in seconds. Advances smoothly between mpv's
+    /// time-pos notifications while playing.
+    pub fn position(&self) -> Option<f64> {
+        let pos = self.time_pos?;
+        if self.paused || self.buffering {
+            return Some(pos);
+        }
+        let since = self.updated.map(|t| t.elapsed().as_secs_f64()).unwrap_or(0.0);
+        Some(pos + since.min(MAX_EXTRAPOLATION))
+    }
+}
+
+/// Bidirectional JSON IPC connection to a running mpv.
+///
+/// A reader thread applies `property-change` events to a shared
+/// [`PlaybackState`] and checks command replies.
+pub struct MpvClient {
+    stream: UnixStream,
+    state: Arc<Mutex<PlaybackState>>,
+    next_id: u64,
+}
+
+impl MpvClient {
+    pub fn connect(path: &str) -> Result<Self, MpvError> {
+        let stream = UnixStream::connect(path)?;
+        let reader = stream.try_clone()?;
+        let state = Arc::new(Mutex::new(PlaybackState::default()));
+
+        let shared = Arc::clone(&state);
+        std::thread::spawn(move || read_loop(reader, shared));
+
+        let mut client = Self { stream, state, next_id: 0 };
+        for (i, name) in OBSERVED.iter().enumerate() {
+            client.command(json!(["observe_property", i + 1, name]))?;
+        }
+        log::info!("Connected to mpv IPC at {}", path);
+        Ok(client)
+    }
+
+    pub fn state(&self) -> PlaybackState {
+        self.state.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// Send a command without waiting for mpv's reply. Failed replies are logged.
+    pub fn command(&mut self, args: Value) -> Result<(), MpvError> {
+        self.next_id += 1;
+        let msg = json!({ "command": args, "request_id": self.next_id });
+        writeln!(self.stream, "{}", msg)?;
+        Ok(())
+    }
+
+    /// Seek to an absolute position and rebase the clock right away, so the
+    /// lyrics jump with the audio instead of waiting for the next time-pos.
+    pub fn seek(&mut self, secs: f64) -> Result<(), MpvError> {
+        self.command(json!(["seek", secs, "absolute+exact"]))?;
+        if let Ok(mut st) = self.state.lock() {
+            st.time_pos = Some(secs);
+            st.eof_reached = false;
+            st.updated = Some(Instant::now());
+        }
+        Ok(())
+    }
+}
+
+impl Drop for MpvClient {
+    fn drop(&mut self) {
+        // unblocks the reader thread
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+fn read_loop(stream: UnixStream, state: Arc<Mutex<PlaybackState>>) {
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else { break };
+        let Ok(msg) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+
+        if msg.get("event").and_then(Value::as_str) == Some("property-change") {
+            let name = msg.get("name").and_then(Value::as_str).unwrap_or_default();
+            let data = msg.get("data").unwrap_or(&Value::Null);
+            if let Ok(mut st) = state.lock() {
+                apply_property(&mut st, name, data);
+            }
+        } else if let Some(id) = msg.get("request_id").and_then(Value::as_u64) {
+            match msg.get("error").and_then(Value::as_str) {
+                Some("success") | None => {}
+                Some(e) => log::warn!("mpv request {} failed: {}", id, e),
+            }
+        }
+    }
+    log::debug!("mpv IPC closed");
+}
+
+fn apply_property(st: &mut PlaybackState, name: &str, data: &Value) {
+    match name {
+        "time-pos" => {
+            st.time_pos = data.as_f64();
+            st.updated = Some(Instant::now());
+        }
+        // freeze the extrapolated position before the clock stops or restarts
+        "pause" => {
+            st.time_pos = st.position();
+            st.paused = data.as_bool().unwrap_or(false);
+            st.updated = Some(Instant::now());
+        }
+        "paused-for-cache" => {
+            st.time_pos = st.position();
+            st.buffering = data.as_bool().unwrap_or(false);
+            st.updated = Some(Instant::now());
+        }
+        "duration" => st.duration = data.as_f64(),
+        "eof-reached" => st.eof_reached = data.as_bool().unwrap_or(false),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// As if the last time-pos update came `ago` seconds back.
+    fn age(st: &mut PlaybackState, ago: f64) {
+        st.updated = Some(Instant::now() - Duration::from_secs_f64(ago));
+    }
+
+    fn near(a: Option<f64>, b: f64) -> bool {
+        a.is_some_and(|a| (a - b).abs() < 0.05)
+    }
+
+    #[test]
+    fn time_pos_and_null() {
+        let mut st = PlaybackState::default();
+        assert_eq!(st.position(), None);
+        apply_property(&mut st, "time-pos", &json!(12.5));
+        assert!(near(st.position(), 12.5));
+        // mpv sends null while no file is loaded
+        apply_property(&mut st, "time-pos", &Value::Null);
+        assert_eq!(st.position(), None);
+
+        apply_property(&mut st, "duration", &json!(200.0));
+        apply_property(&mut st, "eof-reached", &json!(true));
+        assert_eq!((st.duration, st.eof_reached), (Some(200.0), true));
+        apply_property(&mut st, "duration", &Value::Null);
+        assert_eq!(st.duration, None);
+    }
+
+    #[test]
+    fn extrapolates_while_playing_up_to_the_cap() {
+        let mut st = PlaybackState::default();
+        apply_property(&mut st, "time-pos", &json!(10.0));
+        age(&mut st, 0.5);
+        assert!(near(st.position(), 10.5));
+        age(&mut st, 30.0);
+        assert!(near(st.position(), 10.0 + MAX_EXTRAPOLATION));
+    }
+
+    #[test]
+    fn pause_and_buffering_freeze_the_clock() {
+        let mut st = PlaybackState::default();
+        apply_property(&mut st, "time-pos", &json!(10.0));
+        age(&mut st, 0.5);
+        // the extrapolated position is kept, then stands still
+        apply_property(&mut st, "pause", &json!(true));
+        assert!(st.paused);
+        age(&mut st, 5.0);
+        assert!(near(st.position(), 10.5));
+
+        apply_property(&mut st, "pause", &json!(false));
+        age(&mut st, 0.25);
+        assert!(near(st.position(), 10.75));
+
+        apply_property(&mut st, "paused-for-cache", &json!(true));
+        assert!(st.buffering);
+        age(&mut st, 5.0);
+        assert!(near(st.position(), 10.75));
+        apply_property(&mut st, "paused-for-cache", &Value::Null);
+        assert!(!st.buffering);
+    }
+}
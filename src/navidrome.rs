@@ -21,13 +21,39 @@ pub struct Playlist {
     pub song_count: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct PlaylistTrack {
     pub id: String,
     pub title: String,
     pub artist: String,
+    pub album: String,
     pub duration: u32,
     pub cover_art_id: Option<String>,
+    /// Genre or mood tags say there are no vocals.
+    pub instrumental: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct NowPlaying {
+    pub track: PlaylistTrack,
+    pub username: String,
+    pub minutes_ago: u32,
+}
+
+/// One entry of OpenSubsonic `structuredLyrics`.
+#[derive(Debug, Clone)]
+pub struct StructuredLyrics {
+    pub lang: String,
+    pub synced: bool,
+    /// Milliseconds; positive means lines should show sooner.
+    pub offset_ms: i64,
+    pub lines: Vec<StructuredLine>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StructuredLine {
+    pub start_ms: Option<u32>,
+    pub value: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -66,9 +92,39 @@ struct PlaylistTrackEntry {
     id: String,
     title: Option<String>,
     artist: Option<String>,
+    album: Option<String>,
     duration: Option<u32>,
     #[serde(rename = "coverArt")]
     cover_art: Option<String>,
+    genre: Option<String>,
+    /// OpenSubsonic: every genre tag, not just the first.
+    #[serde(default)]
+    genres: Vec<ItemGenre>,
+    #[serde(default)]
+    moods: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItemGenre {
+    name: String,
+}
+
+impl From<PlaylistTrackEntry> for PlaylistTrack {
+    fn from(e: PlaylistTrackEntry) -> Self {
+        let instrumental = e.genre.iter()
+            .chain(e.genres.iter().map(|g| &g.name))
+            .chain(e.moods.iter())
+            .any(|t| t.to_lowercase().contains("instrumental"));
+        PlaylistTrack {
+            id: e.id,
+            title: e.title.unwrap_or_default(),
+            artist: e.artist.unwrap_or_default(),
+            album: e.album.unwrap_or_default(),
+            duration: e.duration.unwrap_or(0),
+            cover_art_id: e.cover_art,
+            instrumental,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -83,6 +139,102 @@ struct PlaylistInner {
     entry: Vec<PlaylistTrackEntry>,
 }
 
+// ---- serde types for getSong ----
+
+#[derive(Debug, Deserialize)]
+struct SongWrapper {
+    status: String,
+    #[serde(default)]
+    song: Option<PlaylistTrackEntry>,
+}
+
+// ---- serde types for getNowPlaying ----
+
+#[derive(Debug, Deserialize)]
+struct NowPlayingEntry {
+    #[serde(flatten)]
+    track: PlaylistTrackEntry,
+    #[serde(default)]
+    username: String,
+    #[serde(rename = "minutesAgo", default)]
+    minutes_ago: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct NowPlayingWrapper {
+    status: String,
+    #[serde(rename = "nowPlaying", default)]
+    now_playing: NowPlayingInner,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NowPlayingInner {
+    #[serde(default)]
+    entry: Vec<NowPlayingEntry>,
+}
+
+// ---- serde types for getOpenSubsonicExtensions ----
+
+#[derive(Debug, Deserialize)]
+struct ExtensionsWrapper {
+    status: String,
+    #[serde(rename = "openSubsonicExtensions", default)]
+    extensions: Vec<ExtensionEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtensionEntry {
+    name: String,
+}
+
+// ---- serde types for getLyricsBySongId ----
+
+#[derive(Debug, Deserialize)]
+struct LyricsListWrapper {
+    status: String,
+    #[serde(rename = "lyricsList", default)]
+    lyrics_list: LyricsListInner,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct LyricsListInner {
+    #[serde(rename = "structuredLyrics", default)]
+    structured: Vec<StructuredLyricsEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StructuredLyricsEntry {
+    #[serde(default)]
+    lang: String,
+    #[serde(default)]
+    synced: bool,
+    #[serde(default)]
+    offset: i64,
+    #[serde(default)]
+    line: Vec<StructuredLineEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StructuredLineEntry {
+    start: Option<u32>,
+    #[serde(default)]
+    value: String,
+}
+
+// ---- serde types for getLyrics ----
+
+#[derive(Debug, Deserialize)]
+struct LegacyLyricsWrapper {
+    status: String,
+    #[serde(default)]
+    lyrics: LegacyLyricsInner,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct LegacyLyricsInner {
+    value: Option<String>,
+}
+
 // ---- helpers ----
 
 fn auth_params(cfg: &Config) -> String {
@@ -124,12 +276,35 @@ pub fn get_playlist_tracks(cfg: &Config, id: &str) -> Result<Vec<PlaylistTrack>,
         return Err(NavidromeError::InvalidResponse);
     }
 
-    Ok(parsed.response.playlist.entry.into_iter().map(|e| PlaylistTrack {
-        id: e.id,
-        title: e.title.unwrap_or_default(),
-        artist: e.artist.unwrap_or_default(),
-        duration: e.duration.unwrap_or(0),
-        cover_art_id: e.cover_art,
+    Ok(parsed.response.playlist.entry.into_iter().map(PlaylistTrack::from).collect())
+}
+
+pub fn get_song(cfg: &Config, id: &str) -> Result<PlaylistTrack, NavidromeError> {
+    let url = format!("{}/rest/getSong?id={}&{}", cfg.navidrome_url, id, auth_params(cfg));
+    let resp = make_client()?.get(url).send()?.error_for_status()?;
+    let parsed: SubsonicResponse<SongWrapper> = resp.json()?;
+
+    if parsed.response.status != "ok" {
+        return Err(NavidromeError::InvalidResponse);
+    }
+
+    parsed.response.song.map(PlaylistTrack::from).ok_or(NavidromeError::InvalidResponse)
+}
+
+/// Tracks currently being streamed from the server, across all users and players.
+pub fn get_now_playing(cfg: &Config) -> Result<Vec<NowPlaying>, NavidromeError> {
+    let url = format!("{}/rest/getNowPlaying?{}", cfg.navidrome_url, auth_params(cfg));
+    let resp = make_client()?.get(url).send()?.error_for_status()?;
+    let parsed: SubsonicResponse<NowPlayingWrapper> = resp.json()?;
+
+    if parsed.response.status != "ok" {
+        return Err(NavidromeError::InvalidResponse);
+    }
+
+    Ok(parsed.response.now_playing.entry.into_iter().map(|e| NowPlaying {
+        track: e.track.into(),
+        username: e.username,
+        minutes_ago: e.minutes_ago,
     }).collect())
 }
 
@@ -149,3 +324,94 @@ pub fn fetch_cover_art_bytes(cfg: &Config, cover_art_id: &str) -> Option<Vec<u8>
         .bytes().ok()?;
     Some(bytes.to_vec())
 }
+
+/// Names of the OpenSubsonic extensions the server advertises (e.g. `songLyrics`).
+/// Plain Subsonic servers answer with an error, which is reported as an empty list.
+pub fn get_open_subsonic_extensions(cfg: &Config) -> Result<Vec<String>, NavidromeError> {
+    let url = format!("{}/rest/getOpenSubsonicExtensions?{}", cfg.navidrome_url, auth_params(cfg));
+    let resp = make_client()?.get(url).send()?.error_for_status()?;
+    let parsed: SubsonicResponse<ExtensionsWrapper> = resp.json()?;
+
+    if parsed.response.status != "ok" {
+        return Ok(vec![]);
+    }
+
+    Ok(parsed.response.extensions.into_iter().map(|e| e.name).collect())
+}
+
+/// OpenSubsonic `getLyricsBySongId`: embedded and sidecar lyrics for a song.
+pub fn get_lyrics_by_song_id(cfg: &Config, id: &str) -> Result<Vec<StructuredLyrics>, NavidromeError> {
+    let url = format!("{}/rest/getLyricsBySongId?id={}&{}", cfg.navidrome_url, id, auth_params(cfg));
+    let resp = make_client()?.get(url).send()?.error_for_status()?;
+    structured_lyrics(resp.json()?)
+}
+
+fn structured_lyrics(parsed: SubsonicResponse<LyricsListWrapper>) -> Result<Vec<StructuredLyrics>, NavidromeError> {
+    if parsed.response.status != "ok" {
+        return Err(NavidromeError::InvalidResponse);
+    }
+
+    Ok(parsed.response.lyrics_list.structured.into_iter().map(|l| StructuredLyrics {
+        lang: l.lang,
+        synced: l.synced,
+        offset_ms: l.offset,
+        lines: l.line.into_iter().map(|ln| StructuredLine {
+            start_ms: ln.start,
+            value: ln.value,
+        }).collect(),
+    }).collect())
+}
+
+/// Legacy Subsonic `getLyrics`: plain text matched by artist and title.
+pub fn get_lyrics(cfg: &Config, artist: &str, title: &str) -> Result<Option<String>, NavidromeError> {
+    let url = format!(
+        "{}/rest/getLyrics?artist={}&title={}&{}",
+        cfg.navidrome_url,
+        urlencoding::encode(artist),
+        urlencoding::encode(title),
+        auth_params(cfg)
+    );
+    let resp = make_client()?.get(url).send()?.error_for_status()?;
+    let parsed: SubsonicResponse<LegacyLyricsWrapper> = resp.json()?;
+
+    if parsed.response.status != "ok" {
+        return Err(NavidromeError::InvalidResponse);
+    }
+
+    Ok(parsed.response.lyrics.value.filter(|v| !v.trim().is_empty()))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A getLyricsBySongId reply: synced English with a word-timed line, a translation
+    /// sharing a timestamp and an offset, plus an unsynced copy.
+    pub(crate) const LYRICS_REPLY: &str = r#"{"subsonic-response":{"status":"ok","version":"1.16.1","openSubsonic":true,
+        "lyricsList":{"structuredLyrics":[
+            {"displayArtist":"Artist","displayTitle":"Song","lang":"eng","offset":-250,"synced":true,"line":[
+                {"start":1000,"value":"<00:01.00>Hello <00:01.50>world"},
+                {"start":3000,"value":"Bye"},
+                {"start":1000,"value":"Hallo Welt"}]},
+            {"lang":"xxx","synced":false,"line":[{"value":"Hello world"},{"value":"Bye"}]}]}}}"#;
+
+    pub(crate) fn lyrics_reply(json: &str) -> Result<Vec<StructuredLyrics>, NavidromeError> {
+        structured_lyrics(serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn structured_lyrics_reply() {
+        let all = lyrics_reply(LYRICS_REPLY).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!((all[0].lang.as_str(), all[0].synced, all[0].offset_ms), ("eng", true, -250));
+        assert_eq!(all[0].lines[1].start_ms, Some(3000));
+        assert_eq!((all[1].lang.as_str(), all[1].synced, all[1].offset_ms), ("xxx", false, 0));
+        assert_eq!(all[1].lines[0].start_ms, None);
+        assert_eq!(all[1].lines[0].value, "Hello world");
+
+        let failed = r#"{"subsonic-response":{"status":"failed","error":{"code":70,"message":"not found"}}}"#;
+        assert!(matches!(lyrics_reply(failed), Err(NavidromeError::InvalidResponse)));
+        let empty = r#"{"subsonic-response":{"status":"ok","lyricsList":{}}}"#;
+        assert!(lyrics_reply(empty).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,308 @@
+// src/romanize.rs
+//! Offline transliteration for lyric lines: kana to Hepburn romaji, hangul to
+//! Revised Romanization, Cyrillic to Latin. Kanji and anything else pass through.
+
+/// `text` in Latin letters, or None when it has no kana, hangul or Cyrillic.
+pub fn romanize(text: &str) -> Option<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut changed = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let script = Script::of(chars[i]);
+        let end = (i..chars.len()).find(|&j| Script::of(chars[j]) != script).unwrap_or(chars.len());
+        let run = &chars[i..end];
+        match script {
+            Script::Kana => out.push_str(&kana(run)),
+            Script::Hangul => out.push_str(&hangul(run)),
+            Script::Cyrillic => out.push_str(&cyrillic(run)),
+            Script::Other => run.iter().for_each(|&c| push_punct(&mut out, c)),
+        }
+        changed |= script != Script::Other;
+        i = end;
+    }
+    changed.then(|| out.split(' ').filter(|w| !w.is_empty()).collect::<Vec<_>>().join(" "))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Script {
+    Kana,
+    Hangul,
+    Cyrillic,
+    Other,
+}
+
+impl Script {
+    fn of(c: char) -> Self {
+        match c {
+            '\u{3041}'..='\u{3096}' | '\u{30a1}'..='\u{30fa}' | 'ー' => Script::Kana,
+            '\u{ac00}'..='\u{d7a3}' => Script::Hangul,
+            '\u{0400}'..='\u{04ff}' => Script::Cyrillic,
+            _ => Script::Other,
+        }
+    }
+}
+
+/// Full-width and CJK punctuation as ASCII.
+fn push_punct(out: &mut String, c: char) {
+    match c {
+        '、' | '，' => out.push_str(", "),
+        '。' => out.push_str(". "),
+        '！' => out.push('!'),
+        '？' => out.push('?'),
+        '「' | '」' | '『' | '』' => out.push('"'),
+        '（' => out.push('('),
+        '）' => out.push(')'),
+        '・' | '\u{3000}' => out.push(' '),
+        '〜' | '～' => out.push('~'),
+        c => out.push(c),
+    }
+}
+
+// ---- kana ----
+
+const KANA: [(char, &str); 89] = [
+    ('あ', "a"), ('い', "i"), ('う', "u"), ('え', "e"), ('お', "o"),
+    ('か', "ka"), ('き', "ki"), ('く', "ku"), ('け', "ke"), ('こ', "ko"),
+    ('が', "ga"), ('ぎ', "gi"), ('ぐ', "gu"), ('げ', "ge"), ('ご', "go"),
+    ('さ', "sa"), ('し', "shi"), ('す', "su"), ('せ', "se"), ('そ', "so"),
+    ('ざ', "za"), ('じ', "ji"), ('ず', "zu"), ('ぜ', "ze"), ('ぞ', "zo"),
+    ('た', "ta"), ('ち', "chi"), ('つ', "tsu"), ('て', "te"), ('と', "to"),
+    ('だ', "da"), ('ぢ', "ji"), ('づ', "zu"), ('で', "de"), ('ど', "do"),
+    ('な', "na"), ('に', "ni"), ('ぬ', "nu"), ('ね', "ne"), ('の', "no"),
+    ('は', "ha"), ('ひ', "hi"), ('ふ', "fu"), ('へ', "he"), ('ほ', "ho"),
+    ('ば', "ba"), ('び', "bi"), ('ぶ', "bu"), ('べ', "be"), ('ぼ', "bo"),
+    ('ぱ', "pa"), ('ぴ', "pi"), ('ぷ', "pu"), ('ぺ', "pe"), ('ぽ', "po"),
+    ('ま', "ma"), ('み', "mi"), ('む', "mu"), ('め', "me"), ('も', "mo"),
+    ('や', "ya"), ('ゆ', "yu"), ('よ', "yo"),
+    ('ら', "ra"), ('り', "ri"), ('る', "ru"), ('れ', "re"), ('ろ', "ro"),
+    ('わ', "wa"), ('ゐ', "i"), ('ゑ', "e"), ('を', "o"), ('ん', "n"), ('ゔ', "vu"),
+    ('ぁ', "a"), ('ぃ', "i"), ('ぅ', "u"), ('ぇ', "e"), ('ぉ', "o"),
+    ('ゃ', "ya"), ('ゅ', "yu"), ('ょ', "yo"), ('ゎ', "wa"), ('ゕ', "ka"), ('ゖ', "ke"),
+    ('ヷ', "va"), ('ヸ', "vi"), ('ヹ', "ve"), ('ヺ', "vo"),
+];
+
+/// Katakana folded onto hiragana; ヷ-ヺ (va row) kept apart since hiragana has none.
+fn hiragana(c: char) -> char {
+    match c {
+        '\u{30a1}'..='\u{30f6}' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        c => c,
+    }
+}
+
+fn kana(run: &[char]) -> String {
+    let mut out = String::new();
+    let mut last = String::new(); // romaji of the previous kana
+    let mut sokuon = false;
+    for &c in run {
+        let h = hiragana(c);
+        let syl = match h {
+            'ー' => {
+                // long vowel mark repeats the vowel before it
+                if let Some(v) = last.chars().last().filter(|v| "aeiou".contains(*v)) {
+                    out.push(v);
+                }
+                continue;
+            }
+            'っ' => {
+                sokuon = true;
+                continue;
+            }
+            // きゃ → kya, しゃ → sha
+            'ゃ' | 'ゅ' | 'ょ' if last.len() > 1 && last.ends_with('i') => {
+                let stem = &last[..last.len() - 1];
+                let y = if ["sh", "ch", "j"].contains(&stem) { "" } else { "y" };
+                format!("{}{}{}", stem, y, &romaji(h)[1..])
+            }
+            // loanword spellings: ファ → fa, ティ → ti, ウィ → wi, チェ → che
+            'ぁ' | 'ぃ' | 'ぅ' | 'ぇ' | 'ぉ' if !last.is_empty() && last != "n" => {
+                let stem = if last == "u" { "w" } else { &last[..last.len() - 1] };
+                format!("{}{}", stem, romaji(h))
+            }
+            h => {
+                if std::mem::take(&mut sokuon) {
+                    // っち → tchi, otherwise the next consonant doubles
+                    match romaji(h).chars().next() {
+                        Some('c') => out.push('t'),
+                        Some(first) if !"aeioun".contains(first) => out.push(first),
+                        _ => {}
+                    }
+                }
+                // ん before a vowel or y is written n' so it doesn't read as na/nya
+                if last == "n" && romaji(h).starts_with(['a', 'e', 'i', 'o', 'u', 'y']) {
+                    out.push('\'');
+                }
+                out.push_str(&romaji(h));
+                last = romaji(h);
+                continue;
+            }
+        };
+        // a small kana rewrites the syllable before it
+        out.truncate(out.len() - last.len());
+        out.push_str(&syl);
+        last = syl;
+    }
+    out
+}
+
+fn romaji(h: char) -> String {
+    match KANA.iter().find(|(k, _)| *k == h) {
+        Some((_, r)) => r.to_string(),
+        None => h.to_string(),
+    }
+}
+
+// ---- hangul ----
+
+const INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p", "h",
+];
+
+const VOWELS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we", "wi", "yu",
+    "eu", "ui", "i",
+];
+
+/// Final consonant before a pause or another consonant.
+const FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "p", "l", "l", "p", "l", "m", "p", "p", "t", "t",
+    "ng", "t", "t", "k", "t", "p", "t",
+];
+
+/// Final consonant followed by a vowel: what stays, and what moves over to become
+/// the next syllable's initial (받아 → bada, 닭이 → dalgi).
+const LIAISON: [(&str, &str); 28] = [
+    ("", ""), ("", "g"), ("", "kk"), ("k", "s"), ("", "n"), ("n", "j"), ("", "n"), ("", "d"), ("", "r"),
+    ("l", "g"), ("l", "m"), ("l", "b"), ("l", "s"), ("l", "t"), ("l", "p"), ("", "r"), ("", "m"), ("", "b"),
+    ("p", "s"), ("", "s"), ("", "ss"), ("ng", ""), ("", "j"), ("", "ch"), ("", "k"), ("", "t"), ("", "p"), ("", ""),
+];
+
+const IEUNG: usize = 11;
+const NIEUN: usize = 2;
+const MIEUM: usize = 6;
+const RIEUL: usize = 5;
+
+fn hangul(run: &[char]) -> String {
+    let syllables: Vec<(usize, usize, usize)> = run
+        .iter()
+        .map(|&c| {
+            let s = c as usize - 0xac00;
+            (s / 588, (s % 588) / 28, s % 28)
+        })
+        .collect();
+
+    let mut out = String::new();
+    let mut carried: Option<String> = None;
+    for (i, &(l, v, t)) in syllables.iter().enumerate() {
+        out.push_str(&carried.take().unwrap_or_else(|| INITIALS[l].to_string()));
+        out.push_str(VOWELS[v]);
+        if t == 0 {
+            continue;
+        }
+
+        let Some(&(next_l, _, _)) = syllables.get(i + 1) else {
+            out.push_str(FINALS[t]);
+            break;
+        };
+        let fin = FINALS[t];
+        let (fin, next_initial) = match next_l {
+            IEUNG => LIAISON[t],
+            // nasalization: 국물 → gungmul, 입니다 → imnida
+            NIEUN | MIEUM => match fin {
+                "k" => ("ng", INITIALS[next_l]),
+                "t" => ("n", INITIALS[next_l]),
+                "p" => ("m", INITIALS[next_l]),
+                "l" if next_l == NIEUN => ("l", "l"),
+                f => (f, INITIALS[next_l]),
+            },
+            // ㄹ after a consonant: 신라 → silla, 종로 → jongno
+            RIEUL => match fin {
+                "l" => ("l", "l"),
+                "n" => ("l", "l"),
+                "k" => ("ng", "n"),
+                "t" => ("n", "n"),
+                "p" => ("m", "n"),
+                f => (f, "n"),
+            },
+            _ => (fin, INITIALS[next_l]),
+        };
+        out.push_str(fin);
+        carried = Some(next_initial.to_string());
+    }
+    out
+}
+
+// ---- cyrillic ----
+
+fn cyrillic(run: &[char]) -> String {
+    let mut out = String::new();
+    for (i, &c) in run.iter().enumerate() {
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        let latin = match lower {
+            'а' => "a", 'б' => "b", 'в' => "v", 'г' => "g", 'д' => "d", 'е' => "e", 'ё' => "yo",
+            'ж' => "zh", 'з' => "z", 'и' => "i", 'й' => "y", 'к' => "k", 'л' => "l", 'м' => "m",
+            'н' => "n", 'о' => "o", 'п' => "p", 'р' => "r", 'с' => "s", 'т' => "t", 'у' => "u",
+            'ф' => "f", 'х' => "kh", 'ц' => "ts", 'ч' => "ch", 'ш' => "sh", 'щ' => "shch",
+            'ъ' | 'ь' => "", 'ы' => "y", 'э' => "e", 'ю' => "yu", 'я' => "ya",
+            // Ukrainian, Belarusian
+            'є' => "ye", 'і' => "i", 'ї' => "yi", 'ґ' => "g", 'ў' => "u",
+            // Serbian, Macedonian
+            'ђ' => "dj", 'ј' => "j", 'љ' => "lj", 'њ' => "nj", 'ћ' => "c", 'џ' => "dz",
+            'ѓ' => "gj", 'ќ' => "kj", 'ѕ' => "dz",
+            _ => {
+                out.push(c);
+                continue;
+            }
+        };
+        if c == lower {
+            out.push_str(latin);
+            continue;
+        }
+        // ЖЖ → ZHZH inside an all-caps word, Ж → Zh otherwise
+        let caps_word = run.get(i + 1).is_some_and(|n| n.is_uppercase())
+            || (i > 0 && run[i - 1].is_uppercase());
+        if caps_word {
+            out.push_str(&latin.to_uppercase());
+        } else {
+            let mut cs = latin.chars();
+            if let Some(first) = cs.next() {
+                out.extend(first.to_uppercase());
+                out.extend(cs);
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn romanizes_kana_hangul_and_cyrillic() {
+        let cases = [
+            ("きょうは、ちょっと", "kyouha, chotto"),
+            ("がっこう まっちゃ", "gakkou matcha"),
+            ("ラーメン", "raamen"),
+            ("ティーンエイジャー", "tiin'eijaa"),
+            ("スウィート チェック", "suwiito chekku"),
+            ("夜に駆ける", "夜ni駆keru"),
+            ("감사합니다", "gamsahamnida"),
+            ("사랑해 신라 국물", "saranghae silla gungmul"),
+            ("닭이 좋아요", "dalgi joayo"),
+            ("Привет, мир", "Privet, mir"),
+            ("ЖЖ Щука", "ZHZH Shchuka"),
+        ];
+        for (input, want) in cases {
+            assert_eq!(romanize(input).as_deref(), Some(want), "romanize({:?})", input);
+        }
+        assert_eq!(romanize("plain English"), None);
+    }
+
+    #[test]
+    fn va_row_katakana_keeps_the_syllable_before_it() {
+        assert_eq!(romanize("アヷ").as_deref(), Some("ava"));
+        assert_eq!(romanize("ヴァイオリン ヸヹヺ").as_deref(), Some("vaiorin vivevo"));
+        assert_eq!(romanize("ッヺ").as_deref(), Some("vvo"));
+    }
+}
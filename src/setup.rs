@@ -14,7 +14,6 @@ use ratatui::{
 };
 use std::io::{self};
 
-use md5;
 use rand::{thread_rng, Rng};
 
 use crate::config::Config;
@@ -148,8 +147,7 @@ pub fn run_setup_wizard() -> Config {
                             navidrome_user: user,
                             navidrome_token: token,
                             navidrome_salt: salt,
-                            refresh_interval: 2,
-                            karaoke_enabled: false,
+                            ..Config::default()
                         };
                     } else {
                         field = match field {
@@ -0,0 +1,255 @@
+// src/worker.rs
+use crossbeam_channel::{unbounded, Receiver, Sender, TryRecvError};
+use image::DynamicImage;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::config::Config;
+use crate::lyrics::{self, Candidate, LyricsCache, LyricsData, LyricsError, LyricsProvider, TrackQuery};
+use crate::navidrome::{self, NavidromeError, NowPlaying, PlaylistTrack};
+
+pub enum Request {
+    /// Cover art and lyrics for the track now showing. Answered with `Reply::Loaded`.
+    /// Only the newest of several queued loads is fetched.
+    Load(PlaylistTrack),
+    /// Free-text lyrics search for the picker. Answered with `Reply::Searched`.
+    Search { track: TrackQuery, query: String },
+    /// Drop a cache entry and look its track up again. Answered with `Reply::Refetched`.
+    Refetch { key: String, track: TrackQuery },
+    /// Follow mode's getNowPlaying poll. Answered with `Reply::NowPlaying`.
+    NowPlaying,
+    /// Tracks coming up next; replaces whatever was still queued for prefetch.
+    Prefetch(Vec<PlaylistTrack>),
+    /// Settings changed: rebuild providers and cache policy.
+    Reconfigure(Config),
+}
+
+pub struct Loaded {
+    pub track_id: String,
+    pub cover: Option<DynamicImage>,
+    pub lyrics: Result<LyricsData, LyricsError>,
+}
+
+pub enum Reply {
+    Loaded(Loaded),
+    Searched {
+        track: TrackQuery,
+        query: String,
+        results: Result<Vec<(f32, Candidate)>, LyricsError>,
+    },
+    Refetched {
+        track: TrackQuery,
+        lyrics: Result<LyricsData, LyricsError>,
+    },
+    NowPlaying(Result<Vec<NowPlaying>, NavidromeError>),
+}
+
+/// Network fetches off the UI thread. Loads (and the searches and refetches asked
+/// for by hand, and follow mode's polls) and prefetches run on separate threads so
+/// a slow prefetch never holds up the track that is playing.
+/// Prefetched lyrics land in the disk cache, cover art in a small memory cache.
+pub struct Worker {
+    load_tx: Sender<Request>,
+    prefetch_tx: Sender<Request>,
+    pub replies: Receiver<Reply>,
+}
+
+/// Cover art bytes by cover id, oldest first.
+type CoverCache = Arc<Mutex<VecDeque<(String, Vec<u8>)>>>;
+
+const COVER_CACHE_SIZE: usize = 8;
+
+impl Worker {
+    pub fn spawn(cfg: &Config) -> Self {
+        let covers: CoverCache = Arc::default();
+        let (reply_tx, replies) = unbounded();
+
+        let (load_tx, load_rx) = unbounded();
+        let (cfg_l, covers_l) = (cfg.clone(), Arc::clone(&covers));
+        std::thread::spawn(move || load_loop(cfg_l, load_rx, reply_tx, covers_l));
+
+        let (prefetch_tx, prefetch_rx) = unbounded();
+        let cfg_p = cfg.clone();
+        std::thread::spawn(move || prefetch_loop(cfg_p, prefetch_rx, covers));
+
+        Self { load_tx, prefetch_tx, replies }
+    }
+
+    pub fn send(&self, req: Request) {
+        let res = match req {
+            Request::Prefetch(_) => self.prefetch_tx.send(req),
+            Request::Reconfigure(ref cfg) => {
+                let _ = self.prefetch_tx.send(Request::Reconfigure(cfg.clone()));
+                self.load_tx.send(req)
+            }
+            _ => self.load_tx.send(req),
+        };
+        if res.is_err() {
+            log::error!("Fetch worker is gone");
+        }
+    }
+}
+
+struct Fetcher {
+    cfg: Config,
+    providers: Vec<Box<dyn LyricsProvider>>,
+    cache: LyricsCache,
+    covers: CoverCache,
+}
+
+impl Fetcher {
+    fn new(cfg: Config, covers: CoverCache) -> Self {
+        let providers = lyrics::providers_from_config(&cfg);
+        let cache = LyricsCache::new(&cfg);
+        Self { cfg, providers, cache, covers }
+    }
+
+    fn lyrics(&self, track: &TrackQuery) -> Result<LyricsData, LyricsError> {
+        lyrics::fetch_lyrics(&self.providers, &self.cache, track)
+    }
+
+    fn cover_bytes(&self, pt: &PlaylistTrack) -> Option<Vec<u8>> {
+        let id = pt.cover_art_id.as_deref()?;
+        if let Some((_, bytes)) = self.covers.lock().ok()?.iter().find(|(k, _)| k == id) {
+            return Some(bytes.clone());
+        }
+        let bytes = navidrome::fetch_cover_art_bytes(&self.cfg, id)?;
+        if let Ok(mut covers) = self.covers.lock() {
+            if covers.len() >= COVER_CACHE_SIZE {
+                covers.pop_front();
+            }
+            covers.push_back((id.to_string(), bytes.clone()));
+        }
+        Some(bytes)
+    }
+}
+
+fn load_loop(cfg: Config, rx: Receiver<Request>, tx: Sender<Reply>, covers: CoverCache) {
+    let mut f = Fetcher::new(cfg, covers);
+    let mut pending: VecDeque<Request> = VecDeque::new();
+    loop {
+        if pending.is_empty() {
+            match rx.recv() {
+                Ok(req) => pending.push_back(req),
+                Err(_) => break,
+            }
+        }
+        pending.extend(rx.try_iter());
+        drop_superseded(&mut pending);
+        let Some(req) = pending.pop_front() else { continue };
+
+        let reply = match req {
+            Request::Load(pt) => {
+                let cover = f.cover_bytes(&pt).and_then(|b| image::load_from_memory(&b).ok());
+                let lyrics = f.lyrics(&TrackQuery::from(&pt));
+                Reply::Loaded(Loaded { track_id: pt.id, cover, lyrics })
+            }
+            Request::Search { track, query } => {
+                let results = lyrics::search_candidates(&f.providers, &track, &query);
+                Reply::Searched { track, query, results }
+            }
+            Request::Refetch { key, track } => {
+                f.cache.remove(&key);
+                let lyrics = f.lyrics(&track);
+                Reply::Refetched { track, lyrics }
+            }
+            Request::NowPlaying => Reply::NowPlaying(navidrome::get_now_playing(&f.cfg)),
+            Request::Reconfigure(cfg) => {
+                f = Fetcher::new(cfg, Arc::clone(&f.covers));
+                continue;
+            }
+            Request::Prefetch(_) => continue,
+        };
+        if tx.send(reply).is_err() {
+            break;
+        }
+    }
+}
+
+/// Keeps only the newest `Load`, since the tracks before it were skipped while
+/// waiting, and the newest `NowPlaying` poll. Everything else stays queued in order.
+fn drop_superseded(pending: &mut VecDeque<Request>) {
+    let is_load = |r: &Request| matches!(r, Request::Load(_));
+    let is_poll = |r: &Request| matches!(r, Request::NowPlaying);
+    for same in [&is_load as &dyn Fn(&Request) -> bool, &is_poll] {
+        let Some(newest) = pending.iter().rposition(same) else { continue };
+        let mut i = 0;
+        pending.retain(|r| {
+            let keep = i == newest || !same(r);
+            i += 1;
+            keep
+        });
+    }
+}
+
+fn prefetch_loop(cfg: Config, rx: Receiver<Request>, covers: CoverCache) {
+    let mut f = Fetcher::new(cfg, covers);
+    let mut queue: VecDeque<PlaylistTrack> = VecDeque::new();
+    loop {
+        // block only when there is nothing left to warm up
+        let req = if queue.is_empty() {
+            rx.recv().map_err(|_| TryRecvError::Disconnected)
+        } else {
+            rx.try_recv()
+        };
+        match req {
+            Ok(Request::Prefetch(tracks)) => queue = tracks.into(),
+            Ok(Request::Reconfigure(cfg)) => f = Fetcher::new(cfg, Arc::clone(&f.covers)),
+            Ok(_) => {}
+            Err(TryRecvError::Disconnected) => break,
+            Err(TryRecvError::Empty) => {
+                let Some(pt) = queue.pop_front() else { continue };
+                log::debug!("Prefetching {} — {}", pt.artist, pt.title);
+                let _ = f.cover_bytes(&pt);
+                if let Err(e) = f.lyrics(&TrackQuery::from(&pt)) {
+                    log::debug!("Prefetch {}: {}", pt.title, e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(id: &str) -> Request {
+        Request::Load(PlaylistTrack { id: id.into(), ..Default::default() })
+    }
+
+    fn describe(pending: &VecDeque<Request>) -> Vec<String> {
+        pending
+            .iter()
+            .map(|r| match r {
+                Request::Load(pt) => format!("load {}", pt.id),
+                Request::Search { query, .. } => format!("search {}", query),
+                Request::Refetch { key, .. } => format!("refetch {}", key),
+                Request::NowPlaying => "now playing".into(),
+                Request::Prefetch(_) => "prefetch".into(),
+                Request::Reconfigure(_) => "reconfigure".into(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn only_the_newest_load_is_kept() {
+        let search = Request::Search { track: TrackQuery::default(), query: "q".into() };
+        let mut pending: VecDeque<Request> =
+            vec![load("a"), search, load("b"), Request::Reconfigure(Config::default()), load("c")].into();
+        drop_superseded(&mut pending);
+        assert_eq!(describe(&pending), vec!["search q", "reconfigure", "load c"]);
+
+        let refetch = Request::Refetch { key: "k".into(), track: TrackQuery::default() };
+        let mut pending: VecDeque<Request> = vec![load("a"), refetch].into();
+        drop_superseded(&mut pending);
+        assert_eq!(describe(&pending), vec!["load a", "refetch k"]);
+    }
+
+    #[test]
+    fn polls_queued_behind_a_slow_load_collapse() {
+        let mut pending: VecDeque<Request> =
+            vec![Request::NowPlaying, load("a"), Request::NowPlaying, load("b"), Request::NowPlaying].into();
+        drop_superseded(&mut pending);
+        assert_eq!(describe(&pending), vec!["load b", "now playing"]);
+    }
+}